@@ -0,0 +1,198 @@
+// Delta query building blocks, based on the work of Frank McSherry:
+// https://github.com/TimelyDataflow/differential-dataflow/tree/master/dogsdogsdogs
+//
+// A delta query maintains a multi-way join by joining the changes of each relation
+// directly against the *arrangements* of the other relations, so no intermediate
+// results of the join have to be arranged (and maintained) at all.
+
+use timely::dataflow::Scope;
+use timely::dataflow::channels::pact::{Exchange, Pipeline};
+use timely::dataflow::operators::Capability;
+use timely::dataflow::operators::generic::Operator;
+use timely::order::PartialOrder;
+
+use differential_dataflow::{AsCollection, Collection, Data, ExchangeData};
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::operators::arrange::Arranged;
+use differential_dataflow::trace::{TraceReader, BatchReader, Cursor};
+
+/// Joins each update of `updates` with the contents of `arrangement` as of the time of the update.
+///
+/// Unlike `join`, the updates are not arranged; each of them is looked up once in the trace of
+/// `arrangement` and then forgotten. If `strict` is set, only the updates of the arrangement which
+/// happened strictly before the update are visible, otherwise the updates at the same time are
+/// visible as well. Choosing `strict` per relation is what makes the sum of the delta terms of a
+/// delta query equal to the change of the whole join.
+///
+/// The timestamp is fixed to `usize`, as the trace may only be compacted up to the time right
+/// before the earliest pending update, otherwise `strict` comparisons would be lost.
+pub fn half_join<G, K, V, D, D2, Tr, L>(
+    updates: &Collection<G, (K, D)>,
+    arrangement: &Arranged<G, Tr>,
+    strict: bool,
+    mut logic: L,
+) -> Collection<G, D2>
+where
+    G: Scope<Timestamp=usize>,
+    K: ExchangeData + Hashable<Output=u64>,
+    V: Data,
+    D: ExchangeData,
+    D2: Data,
+    Tr: TraceReader<Key=K, Val=V, Time=usize, R=isize> + Clone + 'static,
+    Tr::Batch: BatchReader<K, V, usize, isize> + 'static,
+    Tr::Cursor: Cursor<K, V, usize, isize> + 'static,
+    L: FnMut(&K, &D, &V) -> D2 + 'static,
+{
+    let mut trace = Some(arrangement.trace.clone());
+    if let Some(trace) = trace.as_mut() {
+        // batches are never read one by one, so they can be merged freely.
+        trace.distinguish_since(&[]);
+    }
+
+    // updates waiting for the arrangement to catch up with their time.
+    let mut stash: Vec<(Capability<usize>, Vec<((K, D), usize, isize)>)> = Vec::new();
+    let mut buffer = Vec::new();
+
+    // updates have to be on the same worker as the matching keys of the arrangement.
+    let exchange = Exchange::new(|update: &((K, D), usize, isize)| (update.0).0.hashed());
+
+    updates.inner.binary_frontier(&arrangement.stream, exchange, Pipeline, "HalfJoin", move |_cap, _info| {
+        move |input1, input2, output| {
+            input1.for_each(|capability, data| {
+                data.swap(&mut buffer);
+                stash.push((capability.retain(), buffer.drain(..).collect()));
+            });
+
+            // only the frontier of the arrangement is interesting, its contents are read from the trace.
+            input2.for_each(|_capability, _data| { });
+
+            if let Some(ref mut trace) = trace {
+                let frontier = input2.frontier();
+                let (mut cursor, storage) = trace.cursor();
+
+                for (capability, updates) in stash.iter_mut() {
+                    // an update can be answered once the arrangement holds all the updates up to its time.
+                    let (mut ready, pending): (Vec<_>, Vec<_>) = updates
+                        .drain(..)
+                        .partition(|(_data, time, _diff)| !frontier.less_equal(time));
+                    *updates = pending;
+
+                    ready.sort_by(|x, y| (x.0).0.cmp(&(y.0).0));
+                    cursor.rewind_keys(&storage);
+
+                    let mut session = output.session(capability);
+                    for ((key, data), time, diff) in ready.into_iter() {
+                        cursor.seek_key(&storage, &key);
+                        if cursor.get_key(&storage) != Some(&key) {
+                            continue;
+                        }
+
+                        while let Some(val) = cursor.get_val(&storage) {
+                            let mut count = 0;
+                            cursor.map_times(&storage, |t, d| {
+                                if t.less_than(&time) || (!strict && t.eq(&time)) {
+                                    count += d;
+                                }
+                            });
+                            if count != 0 {
+                                session.give((logic(&key, &data, val), time, diff * count));
+                            }
+                            cursor.step_val(&storage);
+                        }
+                    }
+                }
+            }
+
+            stash.retain(|(_capability, updates)| !updates.is_empty());
+
+            // the trace can be compacted up to right before the earliest time which may still be looked up.
+            let earliest = stash
+                .iter()
+                .flat_map(|(_capability, updates)| updates.iter().map(|(_data, time, _diff)| *time))
+                .chain(input1.frontier().frontier().iter().cloned())
+                .min();
+
+            match earliest {
+                Some(time) => {
+                    if let Some(ref mut trace) = trace {
+                        trace.advance_by(&[time.saturating_sub(1)]);
+                    }
+                },
+                None => { trace = None; },
+            }
+        }
+    })
+    .as_collection()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::half_join;
+
+    use differential_dataflow::input::Input;
+    use differential_dataflow::operators::arrange::ArrangeByKey;
+    use timely::dataflow::operators::capture::{Capture, Extract};
+
+    /// Runs `half_join` over the same updates and arrangement changes, and returns its output
+    /// updates as (data, time, diff), sorted.
+    fn run_half_join(strict: bool) -> Vec<((u32, char), usize, isize)> {
+        let captured = timely::execute_directly(move |worker| {
+            let (mut updates, mut arranged, captured) = worker.dataflow::<usize, _, _>(|scope| {
+                let (updates_input, updates) = scope.new_collection::<(u64, u32), isize>();
+                let (arranged_input, arranged) = scope.new_collection::<(u64, char), isize>();
+
+                let captured = half_join(&updates, &arranged.arrange_by_key(), strict, |_key, &d, &v| (d, v))
+                    .inner
+                    .capture();
+
+                (updates_input, arranged_input, captured)
+            });
+
+            // round 0: 'x' is added to the arrangement
+            arranged.insert((1, 'x'));
+            updates.advance_to(1);
+            arranged.advance_to(1);
+
+            // round 1: 'y' is added to the arrangement, at the same time as update 10
+            arranged.insert((1, 'y'));
+            updates.insert((1, 10));
+            updates.advance_to(2);
+            arranged.advance_to(2);
+
+            // round 2: 'x' is removed from the arrangement, at the same time as update 20
+            arranged.remove((1, 'x'));
+            updates.insert((1, 20));
+            updates.advance_to(3);
+            arranged.advance_to(3);
+
+            // round 3: update 10 is retracted
+            updates.remove((1, 10));
+
+            captured
+        });
+
+        let mut result: Vec<_> = captured.extract().into_iter().flat_map(|(_time, data)| data).collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn half_join_sees_updates_at_the_same_time() {
+        assert_eq!(run_half_join(false), vec![
+            ((10, 'x'), 1, 1),
+            ((10, 'y'), 1, 1),
+            ((10, 'y'), 3, -1),
+            ((20, 'y'), 2, 1),
+        ]);
+    }
+
+    #[test]
+    fn half_join_strict_sees_only_earlier_updates() {
+        assert_eq!(run_half_join(true), vec![
+            ((10, 'x'), 1, 1),
+            ((10, 'y'), 3, -1),
+            ((20, 'x'), 2, 1),
+            ((20, 'y'), 2, 1),
+        ]);
+    }
+}
@@ -8,10 +8,35 @@ use differential_dataflow::input::InputSession;
 use chrono::Utc;
 use differential_dataflow::trace::{TraceReader, BatchReader, Cursor};
 
+use super::types::{Date, Id, Place, Connection, DynamicConnection, Tag, TagClass, Post, Comment};
+
+/// Length of a day in `Date` units (seconds).
+pub const DAY: Date = 24 * 60 * 60;
+
 pub fn format_timestamp(timestamp: u64) -> String {
     chrono::DateTime::<Utc>::from(std::time::UNIX_EPOCH + std::time::Duration::from_secs(timestamp)).to_rfc3339()
 }
 
+/// Returns the index of the `size` long window containing `date`, counting windows from `start`.
+///
+/// Dates before `start` fall into negative windows, e.g. `start - 1` is in window -1.
+pub fn date_bucket(date: Date, start: Date, size: Date) -> i64 {
+    (date - start).div_euclid(size)
+}
+
+/// Returns the number of months from `start` to `end`, counting partial months on both ends
+/// as full months, e.g. from 2010-01-31 to 2010-03-01 is 3 months.
+pub fn months_between(start: Date, end: Date) -> i64 {
+    use chrono::Datelike;
+
+    let month_index = |date: Date| {
+        let date = chrono::NaiveDateTime::from_timestamp(date, 0);
+        date.year() as i64 * 12 + date.month0() as i64
+    };
+
+    month_index(end) - month_index(start) + 1
+}
+
 // https://github.com/frankmcsherry/ttc2018liveContest/blob/aa622a83cc889bab9c6844e1e7db32d2738e6f6e/solutions/differential/src/main.rs#L476
 /// Return the top `limit` distinct keys in input, by keys.
 pub fn limit<G, K, V>(input: &Collection<G,(K,V)>, limit: usize) -> Collection<G,Vec<V>>
@@ -51,6 +76,633 @@ where
         .map(|(_hash, vec)| vec)
 }
 
+/// Return the top `limit` values for each key of input, by values.
+///
+/// Unlike `limit`, which selects a single global top-k, the values are kept in
+/// the collection next to their keys, so groups can be processed further.
+///
+/// The reduce keeps every value of a key, so when an update pushes a value out
+/// of the top `limit`, retracting that update lets the value back in.
+pub fn limit_per_key<G, K, V>(input: &Collection<G,(K,V)>, limit: usize) -> Collection<G,(K,V)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+    K: ExchangeData+std::hash::Hash,
+    V: ExchangeData,
+{
+    use differential_dataflow::operators::reduce::Reduce;
+
+    input
+        .reduce(move |_key, input, output| {
+            for (val, _wgt) in input.iter().take(limit) {
+                output.push(((*val).clone(), 1));
+            }
+        })
+}
+
+/// Returns the posts and comments as (message_id, (created, content)) pairs.
+///
+/// Posts with an image have no text content, for them the content is the image file.
+pub fn message_contents<G>(
+    post: &Collection<G, Post>,
+    comment: &Collection<G, Comment>,
+) -> Collection<G, (Id, (Date, String))>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    post
+        .map(|post| {
+            let content = if post.content().is_empty() { post.image().clone() } else { post.content().clone() };
+            (post.id().clone(), (post.created().clone(), content))
+        })
+        .concat(
+            &comment.map(|comment| (comment.id().clone(), (comment.created().clone(), comment.content().clone())))
+        )
+}
+
+/// Returns the ids of the cities located in the country called `country`.
+pub fn country_cities<G>(
+    place: &Collection<G, Place>,
+    place_is_part_of_place: &Collection<G, Connection>,
+    country: String,
+) -> Collection<G, Id>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::Join;
+
+    place
+        .filter(move |x| country.eq(x.name()))
+        .map(|x| (x.id().clone(), ()))
+        .join_map(
+            &place_is_part_of_place.map(|x| (x.b().clone(), x.a().clone())),
+            |_country, _dummy, city| city.clone()
+        )
+}
+
+/// Returns the place of the given type ("city", "country" or "continent") containing each place.
+///
+/// The result is a collection of (place_id, container_id) pairs, where a place of the given type
+/// is contained by itself, e.g. a city is mapped to its continent via its country.
+pub fn containing_places<G>(
+    place: &Collection<G, Place>,
+    place_is_part_of_place: &Collection<G, Connection>,
+    type_: String,
+) -> Collection<G, (Id, Id)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Join, Iterate, Threshold};
+
+    let places = place.map(|x| (x.id().clone(), x.id().clone()));
+
+    places
+        .iterate(|transitive| {
+            let places = places.enter(&transitive.scope());
+            let parents = place_is_part_of_place
+                .map(|x| (x.a().clone(), x.b().clone())) // -> place_id, parent_id
+                .enter(&transitive.scope());
+
+            transitive
+                .map(|(place_id, ancestor_id)| (ancestor_id, place_id))
+                .join_map(&parents, |_ancestor_id, place_id, parent_id| (place_id.clone(), parent_id.clone()))
+                .concat(&places)
+                .distinct()
+        })
+        .map(|(place_id, ancestor_id)| (ancestor_id, place_id))
+        .semijoin(
+            &place
+                .filter(move |x| type_.eq(x.type_()))
+                .map(|x| x.id().clone())
+        )
+        .map(|(container_id, place_id)| (place_id, container_id))
+}
+
+/// Returns the messages (posts and comments) which have the Tag called `tag_name`.
+///
+/// The result is a collection of (message_id, creator_id) pairs.
+pub fn tagged_messages<G>(
+    tag: &Collection<G, Tag>,
+    has_tag: &Collection<G, DynamicConnection>,
+    has_creator: &Collection<G, DynamicConnection>,
+    tag_name: String,
+) -> Collection<G, (Id, Id)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::Join;
+
+    // get the id of the given Tag.
+    let needed_tag = tag
+        .filter(move |x| tag_name.eq(x.name()))
+        .map(|tag| tag.id().clone());
+
+    has_tag
+        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, message_id
+        .semijoin(&needed_tag)
+        .map(|(_tag_id, message_id)| (message_id, ()))
+        .join_map(
+            &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+            |message_id, _dummy, person_id| (message_id.clone(), person_id.clone())
+        )
+}
+
+/// Returns the ids of the Tags which have the TagClass called `tag_class` as their type.
+pub fn tag_class_tags<G>(
+    tag_classes: &Collection<G, TagClass>,
+    tag_hastype_tagclass: &Collection<G, Connection>,
+    tag_class: String,
+) -> Collection<G, Id>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::Join;
+
+    tag_classes
+        // filter for selected TagClass
+        .filter(move |x| tag_class.eq(x.name()))
+        .map(|tag| (tag.id().clone(), ()))
+        // join Tag based on TagClass
+        .join_map(
+            &tag_hastype_tagclass.map(|conn| (conn.b().clone(), conn.a().clone())),
+            |_tag_class_id, _dummy, c| c.clone(),
+        )
+}
+
+/// Returns the TagClass hierarchy as (tag_class_id, descendant_id) pairs.
+///
+/// Descendants are the direct and indirect subclasses of a TagClass, and the TagClass itself,
+/// following the (subclass_id, superclass_id) connections of `tag_class_is_subclass_of`.
+pub fn tag_class_descendants<G>(
+    tag_classes: &Collection<G, TagClass>,
+    tag_class_is_subclass_of: &Collection<G, Connection>,
+) -> Collection<G, (Id, Id)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Join, Iterate, Threshold};
+
+    let roots = tag_classes.map(|x| (x.id().clone(), x.id().clone()));
+
+    roots.iterate(|transitive| {
+        let roots = roots.enter(&transitive.scope());
+        let subclasses = tag_class_is_subclass_of
+            .map(|x| (x.b().clone(), x.a().clone())) // -> superclass_id, subclass_id
+            .enter(&transitive.scope());
+
+        transitive
+            .map(|(tag_class_id, descendant_id)| (descendant_id, tag_class_id))
+            .join_map(&subclasses, |_descendant_id, tag_class_id, subclass_id| (tag_class_id.clone(), subclass_id.clone()))
+            .concat(&roots)
+            .distinct()
+    })
+}
+
+/// Returns the nodes reachable from `roots` in at least `min_hops` and at most `max_hops` steps.
+///
+/// The result is a collection of ((root, node), distance), where distance is the length of
+/// the shortest path from root to node. Unlike the unbounded iterations of q15 and q19,
+/// paths are not expanded further than `max_hops`, so only the bounded neighbourhood is explored.
+pub fn bounded_reachability<G>(
+    edges: &Collection<G, (Id, Id)>,
+    roots: &Collection<G, Id>,
+    min_hops: u32,
+    max_hops: u32,
+) -> Collection<G, ((Id, Id), u32)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Join, Iterate, Reduce};
+
+    // ((root, root), 0) represents an initial path from root to root with 0 length.
+    let nodes = roots.map(|root| ((root, root), 0));
+
+    nodes
+        .iterate(|dists| {
+            let edges = edges.enter(&dists.scope());
+            let nodes = nodes.enter(&dists.scope());
+
+            dists // -> ((root, node), distance)
+                // only paths shorter than the upper bound can be extended
+                .filter(move |(_root_node, distance)| *distance < max_hops)
+                .map(|((root, node), distance)| (node, (root, distance)))
+                .join_map(
+                    &edges,
+                    |_node, &(root, distance), &next| ((root, next), distance + 1)
+                )
+                .concat(&nodes)
+                // keep only the shortest distance between two nodes
+                .reduce(|_key, input, output| output.push((*input[0].0, 1)))
+        })
+        .filter(move |(_root_node, distance)| min_hops <= *distance)
+}
+
+/// Returns the 2-hop friend circle of `person` over the undirected knows relation.
+///
+/// The result is a collection of (person_id, distance) pairs, where distance is 1 for friends
+/// and 2 for friends of friends. The start person itself is not part of its circle.
+pub fn two_hop_friends<G>(
+    knows: &Collection<G, DynamicConnection>,
+    person: Id,
+) -> Collection<G, (Id, u32)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::Threshold;
+
+    // bidirectional knows relation
+    let bi_knows = knows
+        .map(|conn| (conn.b().clone(), conn.a().clone()))
+        .concat(
+            &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+        );
+
+    // the start person is the only root of the search
+    let root = bi_knows
+        .filter(move |(person_id, _friend_id)| person.eq(person_id))
+        .map(|(person_id, _friend_id)| person_id)
+        .distinct();
+
+    bounded_reachability(&bi_knows, &root, 1, 2)
+        .map(|((_root, person_id), distance)| (person_id, distance))
+}
+
+/// Returns the length of the shortest weighted paths from each of `roots` to every reachable node.
+///
+/// `edges` are directed (src, (dst, weight)) pairs with positive weights. The result is a
+/// collection of ((root, node), distance).
+pub fn weighted_distances<G>(
+    edges: &Collection<G, (Id, (Id, isize))>,
+    roots: &Collection<G, Id>,
+) -> Collection<G, ((Id, Id), isize)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::AsCollection;
+    use differential_dataflow::operators::{Join, Iterate, Reduce};
+    use timely::dataflow::operators::{Map, Delay};
+
+    // ((root, root), 0) represents an initial path from root to root with 0 weight.
+    let nodes = roots.map(|root| ((root, root), 0));
+
+    // based on https://github.com/frankmcsherry/blog/blob/master/posts/2019-05-20.md
+    nodes
+        .iterate(|dists| {
+            let edges = edges.enter(&dists.scope());
+            let nodes = nodes.enter(&dists.scope());
+
+            dists // -> ((root, node), distance)
+                .map(|((root, node), distance)| (node, (root, distance)))
+                .join_map(
+                    &edges,
+                    |_node, &(root, distance), &(next, weight)| ((root, next), distance + weight)
+                )
+                .concat(&nodes)
+
+                // Timely magic, to speed up updates with some time manipulation (see blogpost above)
+                .inner
+                .map_in_place(|((_root_node, distance), t, _r)|
+                    t.inner = std::cmp::max(t.inner, *distance as u64)
+                )
+                .delay(|(_, t, _), _| t.clone())
+                .as_collection()
+
+                // keep only the shortest distance between two nodes
+                .reduce(|_key, input, output| output.push((*input[0].0, 1)))
+        })
+}
+
+/// Returns all the shortest weighted paths between the (src, dst) pairs of `goals`.
+///
+/// The result is a collection of ((src, dst), (path, distance)), where path lists the nodes
+/// from src to dst. An edge is on a shortest path if the distance from src to its source,
+/// its weight and the distance from its target to dst add up to the distance of the goal,
+/// so only the paths along these edges are enumerated.
+pub fn weighted_shortest_paths<G>(
+    edges: &Collection<G, (Id, (Id, isize))>,
+    goals: &Collection<G, (Id, Id)>,
+) -> Collection<G, ((Id, Id), (Vec<Id>, isize))>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Join, Iterate, Threshold};
+
+    let forward = weighted_distances(edges, &goals.map(|(src, _dst)| src).distinct());
+    let reverse = weighted_distances(
+        &edges.map(|(src, (dst, weight))| (dst, (src, weight))),
+        &goals.map(|(_src, dst)| dst).distinct(),
+    );
+
+    // distance of each goal
+    let distances = forward
+        .semijoin(goals)
+        ; // -> ((src, dst), distance)
+
+    // edges (node, next) on the shortest paths of each goal
+    let path_edges = forward
+        .map(|((src, node), distance)| (node, (src, distance)))
+        .join_map(edges, |&node, &(src, distance), &(next, weight)| (src, (node, next, distance + weight)))
+        .join_map(goals, |&src, &(node, next, distance), &dst| ((dst, next), (src, node, distance)))
+        .join_map(&reverse, |&(dst, next), &(src, node, distance), &rest| ((src, dst), (node, next, distance + rest)))
+        .join_map(&distances, |&goal, &(node, next, distance), &shortest| (goal, node, next, distance == shortest))
+        .filter(|(_goal, _node, _next, on_path)| *on_path)
+        .map(|(goal, node, next, _on_path)| ((goal, node), next))
+        ;
+
+    // extend the paths from src along the edges of the shortest paths, until they reach dst
+    let starts = goals.map(|(src, dst)| ((src, dst), vec![src]));
+
+    starts
+        .iterate(|paths| {
+            let path_edges = path_edges.enter(&paths.scope());
+            let starts = starts.enter(&paths.scope());
+
+            paths
+                .map(|(goal, path)| ((goal, *path.last().unwrap()), path))
+                .join_map(&path_edges, |&(goal, _node), path, &next| {
+                    let mut path = path.clone();
+                    path.push(next);
+                    (goal, path)
+                })
+                .concat(&starts)
+                .distinct()
+        })
+        .filter(|((_src, dst), path)| path.last() == Some(dst))
+        .join_map(&distances, |&goal, path, &distance| (goal, (path.clone(), distance)))
+}
+
+/// Describes shortest paths between query node pairs.
+///
+/// The `bijkstra` method takes as input a collection of edges (described as pairs
+/// of source and target node) and a collection of query node pairs (again, source
+/// and target nodes). Its intent is to describe the set of shortest paths between
+/// each pair of query nodes.
+///
+/// The method's output describes a set of directed edges for each input query pair.
+/// The set of directed edges are the directed acyclic graph of edges on shortest
+/// paths from the source of the query pair to the target of the query pair.
+/// There may be multiple paths, and the paths may fork apart and merge together.
+pub fn shortest_paths<G>(
+    edges: &Collection<G, (Id, Id)>,
+    goals: &Collection<G, (Id, Id)>,
+) -> Collection<G, ((Id, Id), (Id, Id))>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    bidirectional_search(edges, goals).0
+}
+
+/// Returns the length of the shortest path between query node pairs.
+///
+/// Uses the same search as `shortest_paths`, so the output is ((src, dst), length) for
+/// each query pair where dst is reachable from src. Pairs with src == dst have length 0,
+/// and pairs which cannot reach each other are missing from the output.
+pub fn shortest_path_lengths<G>(
+    edges: &Collection<G, (Id, Id)>,
+    goals: &Collection<G, (Id, Id)>,
+) -> Collection<G, ((Id, Id), u32)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    bidirectional_search(edges, goals).1
+}
+
+/// Bidirectional search behind `shortest_paths` and `shortest_path_lengths`,
+/// returning the edges on the shortest paths and the lengths of the shortest paths.
+fn bidirectional_search<G>(
+    edges: &Collection<G, (Id, Id)>,
+    goals: &Collection<G, (Id, Id)>,
+) -> (Collection<G, ((Id, Id), (Id, Id))>, Collection<G, ((Id, Id), u32)>)
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Consolidate, Join, Reduce, Threshold};
+    use differential_dataflow::operators::iterate::Variable;
+    use timely::order::Product;
+
+    // Iteratively develop reachability information.
+    edges.scope().iterative::<u64, _, _>(|inner| {
+
+        // Our plan is to start evolving distances from both sources and destinations.
+        // The evolution from a source or destination should continue as long as there
+        // is a corresponding destination or source that has not yet been reached.
+
+        let goals = goals.enter(inner);
+        let edges = edges.enter(inner);
+
+        // forward: ((mid1,mid2), (src, len)) can be read as
+        //      src -len-> mid1 -> mid2 is a shortest path from src to mid2.
+        let forward = Variable::new(inner, Product::new(Default::default(), 1));
+        // reverse: ((mid1,mid2), (dst, len)) can be read as
+        //      mid1 -> mid2 -len-> dst is a shortest path from mid1 to dst.
+        let reverse = Variable::new(inner, Product::new(Default::default(), 1));
+
+        // reached((src, dst), (mid1, mid2)) can be read as
+        //      src -*-> mid1 -> mid2 -*-> dst is a shortest path.
+        let meetings =
+        forward
+            .join_map(&reverse, |&(m1,m2), &(src,len1), &(dst,len2)| {
+                ((src, dst), (len1 + len2, (m1,m2)))
+            })
+            .semijoin(&goals);
+
+        let reached: Collection<_, ((Id, Id), (Id, Id))> =
+        meetings
+            .reduce(|&_src_dst, source, target| {
+                let min_len = (source[0].0).0;
+                for &(&(len,edge),_wgt) in source.iter() {
+                    if len == min_len {
+                        target.push((edge, 1));
+                    }
+                }
+            });
+
+        // lengths((src, dst), len) is the length of the shortest paths from src to dst.
+        // Both sides count the edge they meet on, except for the initial (x, x) edge when src == dst.
+        let lengths: Collection<_, ((Id, Id), u32)> =
+        meetings
+            .reduce(|&(src, dst), source, target| {
+                let min_len = (source[0].0).0;
+                let len = if src == dst { 0 } else { min_len - 1 };
+                target.push((len as u32, 1));
+            });
+
+        // reached.inspect(|x| println!("\tREACHED\t{:?}", x));
+
+        // Subtract from goals any goal pairs that can reach each other.
+        let active =
+        reached
+            .map(|((src,dst),_mid)| (src,dst))
+            .distinct()
+            .negate()
+            .concat(&goals)
+            .consolidate();
+
+        // Let's expand out forward queries that are active.
+        let forward_active = active.map(|(x, _y)| x).distinct();
+        let forward_next = forward
+            .map(|((_mid0,mid1), (src, len))| (src, (mid1, len)))
+            .semijoin(&forward_active)
+            .map(|(src, (mid1, len))| (mid1, (src, len)))
+            .join_map(&edges, |&mid1, &(src, len), &mid2| {
+                ((mid1,mid2), (src, len + 1))
+            })
+            .concat(&*forward)
+            .map(|((mid1,mid2),(src,len))| ((mid2,src),(len,mid1)))
+            .reduce(|_key, s, t| {
+                let min_len = (s[0].0).0;
+                for (&(len,mid1), _weight) in s.iter() {
+                    if len == min_len {
+                        t.push(((len, mid1), 1));
+                    }
+                }
+            })
+            .map(|((mid2, src), (len, mid1))| ((mid1,mid2),(src,len)))
+            ;
+
+        // Let's expand out reverse queries that are active.
+        let reverse_active = active.map(|(_x, y)| y).distinct();
+        let reverse_next = reverse
+            .map(|((mid1,_mid2), (rev, len))| (rev, (mid1, len)))
+            .semijoin(&reverse_active)
+            .map(|(rev, (mid1, len))| (mid1, (rev, len)))
+            .join_map(&edges.map(|(x, y)| (y, x)), |&mid1, &(rev, len), &mid0| {
+                ((mid0,mid1), (rev, len + 1))
+            })
+            .concat(&reverse)
+            .map(|((mid0,mid1),(rev,len))| ((mid0,rev),(len,mid1)))
+            // .map(|(edge, (rev, len))| ((edge, rev), len))
+            .reduce(|_key, s, t| {
+                let min_len = (s[0].0).0;
+                for (&(len,mid1), _weight) in s.iter() {
+                    if len == min_len {
+                        t.push(((len, mid1), 1));
+                    }
+                }
+            })
+            .map(|((mid0, rev), (len, mid1))| ((mid0,mid1), (rev, len)));
+
+        // `reached` are edges on a shortest path;
+        // we want to unwind them back to their roots.
+
+        // reached((src, dst), (mid1, mid2)) means that
+        //      src -*-> mid1 -> mid2 -*-> dst is a shortest path.
+
+        // forward_back(src, dst, )
+        let shortest = Variable::new(inner, Product::new(Default::default(), 1));
+
+        let forward_dag = forward.map(|((mid1,mid2),(src,_len))| ((src,mid2),mid1));
+        let reverse_dag = reverse.map(|((mid1,mid2),(dst,_len))| ((dst,mid1),mid2));
+
+        let short_forward =
+        shortest
+            .map(|((src,dst),(mid1,_mid2))| ((src,mid1),dst))
+            .join_map(&forward_dag, |&(src,mid1),&dst,&mid0| ((src,dst),(mid0,mid1)));
+
+        let short_reverse =
+        shortest
+            .map(|((src,dst),(_mid0,mid1))| ((dst,mid1),src))
+            .join_map(&reverse_dag, |&(dst,mid1),&src,&mid2| ((src,dst),(mid1,mid2)));
+
+        let short =
+        short_forward
+            .concat(&short_reverse)
+            .concat(&reached)
+            .distinct();
+
+        shortest.set(&short);
+
+        forward.set(&forward_next.concat(&goals.map(|(x, _)| ((x,x),(x,0)))));
+        reverse.set(&reverse_next.concat(&goals.map(|(_, y)| ((y,y),(y,0)))));
+
+        (
+            short
+                .filter(|(_,(x,y))| x != y)
+                .leave(),
+            lengths.leave()
+        )
+    })
+}
+
+/// Enumerates the shortest paths between query node pairs.
+///
+/// `shortest_edges` are the edges on shortest paths of each goal, as returned by
+/// `shortest_paths`. Paths are extended from the source along these edges, each of
+/// which leads closer to the destination, so the result is a collection of
+/// ((src, dst), path) with a path for each distinct shortest path from src to dst.
+pub fn enumerate_shortest_paths<G>(
+    goals: &Collection<G, (Id, Id)>,
+    shortest_edges: &Collection<G, ((Id, Id), (Id, Id))>,
+) -> Collection<G, ((Id, Id), Vec<Id>)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    use differential_dataflow::operators::{Join, Iterate, Threshold};
+
+    let path_edges = shortest_edges
+        .map(|((src, dst), (mid1, mid2))| (((src, dst), mid1), mid2));
+
+    let starts = goals.map(|(src, dst)| ((src, dst), vec![src]));
+
+    starts
+        .iterate(|paths| {
+            let path_edges = path_edges.enter(&paths.scope());
+            let starts = starts.enter(&paths.scope());
+
+            paths
+                .map(|(goal, path)| ((goal, *path.last().unwrap()), path))
+                .join_map(&path_edges, |&(goal, _mid1), path, &mid2| {
+                    let mut path = path.clone();
+                    path.push(mid2);
+                    (goal, path)
+                })
+                .concat(&starts)
+                .distinct()
+        })
+        .filter(|((_src, dst), path)| path.last() == Some(dst))
+}
+
+/// Left outer join of `left` and `right`.
+///
+/// Each value of `left` is paired with `Some` of each matching value of `right`,
+/// or with `None` if there is no value in `right` with the same key.
+pub fn left_outer_join<G, K, V1, V2>(
+    left: &Collection<G, (K, V1)>,
+    right: &Collection<G, (K, V2)>,
+) -> Collection<G, (K, (V1, Option<V2>))>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+    K: ExchangeData + std::hash::Hash,
+    V1: ExchangeData,
+    V2: ExchangeData,
+{
+    use differential_dataflow::operators::{Join, Threshold};
+
+    let matched = left
+        .join_map(right, |key, v1, v2| (key.clone(), (v1.clone(), Some(v2.clone()))));
+
+    let unmatched = left
+        .antijoin(&right.map(|(key, _v2)| key).distinct())
+        .map(|(key, v1)| (key, (v1, None)));
+
+    matched.concat(&unmatched)
+}
+
 // Adds a vector to the InputSession and advances time.
 pub fn input_insert_vec<T: Data>(data: Vec<T>, input: &mut InputSession<usize, T, isize>, next_time: usize) {
     for element in data {
@@ -90,3 +742,142 @@ where
         println!("Failed to get cursor :(")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use differential_dataflow::input::Input;
+    use timely::dataflow::operators::capture::{Capture, Extract, Event};
+
+    /// Returns the contents of a captured collection after each round in `0..rounds`.
+    fn contents_by_round<D: Ord + Clone>(
+        captured: std::sync::mpsc::Receiver<Event<usize, (D, usize, isize)>>,
+        rounds: usize,
+    ) -> Vec<Vec<(D, isize)>> {
+        let updates: Vec<_> = captured.extract().into_iter().flat_map(|(_time, data)| data).collect();
+
+        (0..rounds)
+            .map(|round| {
+                let mut contents: Vec<_> = updates
+                    .iter()
+                    .filter(|(_data, time, _diff)| *time <= round)
+                    .map(|(data, _time, diff)| (data.clone(), *diff))
+                    .collect();
+                differential_dataflow::consolidation::consolidate(&mut contents);
+                contents
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounded_reachability_follows_edge_changes() {
+        let captured = timely::execute_directly(|worker| {
+            let (mut edges, mut roots, captured) = worker.dataflow::<usize, _, _>(|scope| {
+                let (edges_input, edges) = scope.new_collection::<(Id, Id), isize>();
+                let (roots_input, roots) = scope.new_collection::<Id, isize>();
+
+                let captured = bounded_reachability(&edges, &roots, 1, 2).inner.capture();
+
+                (edges_input, roots_input, captured)
+            });
+
+            // round 0: a path 1 -> 2 -> 3 -> 4, and a cycle back to the root
+            roots.insert(1);
+            edges.insert((1, 2));
+            edges.insert((2, 3));
+            edges.insert((3, 4));
+            edges.insert((2, 1));
+            edges.advance_to(1);
+            roots.advance_to(1);
+
+            // round 1: a shortcut to 4
+            edges.insert((1, 4));
+            edges.advance_to(2);
+            roots.advance_to(2);
+
+            // round 2: the path is cut after 2
+            edges.remove((2, 3));
+
+            captured
+        });
+
+        assert_eq!(contents_by_round(captured, 3), vec![
+            vec![(((1, 2), 1), 1), (((1, 3), 2), 1)],
+            vec![(((1, 2), 1), 1), (((1, 3), 2), 1), (((1, 4), 1), 1)],
+            vec![(((1, 2), 1), 1), (((1, 4), 1), 1)],
+        ]);
+    }
+
+    #[test]
+    fn left_outer_join_follows_late_right_matches() {
+        let captured = timely::execute_directly(|worker| {
+            let (mut left, mut right, captured) = worker.dataflow::<usize, _, _>(|scope| {
+                let (left_input, left) = scope.new_collection::<(u32, char), isize>();
+                let (right_input, right) = scope.new_collection::<(u32, u32), isize>();
+
+                let captured = left_outer_join(&left, &right).inner.capture();
+
+                (left_input, right_input, captured)
+            });
+
+            // round 0: no right rows yet
+            left.insert((1, 'a'));
+            left.insert((2, 'b'));
+            left.advance_to(1);
+            right.advance_to(1);
+
+            // round 1: a match for the first left row arrives
+            right.insert((1, 10));
+            left.advance_to(2);
+            right.advance_to(2);
+
+            // round 2: and is retracted again
+            right.remove((1, 10));
+
+            captured
+        });
+
+        assert_eq!(contents_by_round(captured, 3), vec![
+            vec![((1, ('a', None)), 1), ((2, ('b', None)), 1)],
+            vec![((1, ('a', Some(10))), 1), ((2, ('b', None)), 1)],
+            vec![((1, ('a', None)), 1), ((2, ('b', None)), 1)],
+        ]);
+    }
+
+    #[test]
+    fn limit_per_key_breaks_ties_by_value() {
+        let captured = timely::execute_directly(|worker| {
+            let (mut input, captured) = worker.dataflow::<usize, _, _>(|scope| {
+                let (input, values) = scope.new_collection::<(char, (u32, u32)), isize>();
+
+                let captured = limit_per_key(&values, 3).inner.capture();
+
+                (input, captured)
+            });
+
+            // round 0: two values tie at the boundary, the smaller one is kept
+            input.insert(('k', (1, 100)));
+            input.insert(('k', (2, 201)));
+            input.insert(('k', (2, 200)));
+            input.insert(('k', (3, 300)));
+            input.insert(('l', (9, 900)));
+            input.advance_to(1);
+
+            // round 1: a new smallest value pushes the larger tied one out
+            input.insert(('k', (0, 50)));
+            input.advance_to(2);
+
+            // round 2: removing a kept value lets the pushed out one back in
+            input.remove(('k', (1, 100)));
+
+            captured
+        });
+
+        assert_eq!(contents_by_round(captured, 3), vec![
+            vec![(('k', (1, 100)), 1), (('k', (2, 200)), 1), (('k', (2, 201)), 1), (('l', (9, 900)), 1)],
+            vec![(('k', (0, 50)), 1), (('k', (1, 100)), 1), (('k', (2, 200)), 1), (('l', (9, 900)), 1)],
+            vec![(('k', (0, 50)), 1), (('k', (2, 200)), 1), (('k', (2, 201)), 1), (('l', (9, 900)), 1)],
+        ]);
+    }
+}
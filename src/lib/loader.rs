@@ -31,6 +31,11 @@ pub fn parse_datetime(date: String) -> Date {
     return unwrap!(parsed, "Failed to parse DateTime: '{}'", date).timestamp();
 }
 
+pub fn parse_date(date: String) -> chrono::NaiveDate {
+    let parsed = chrono::NaiveDate::parse_from_str(date.as_str(), "%Y-%m-%d");
+    return unwrap!(parsed, "Failed to parse Date: '{}'", date);
+}
+
 pub fn load_person(base_path: &str, index: usize, peers: usize) -> Vec<Person> {
     let data = load_data(&format!("{}dynamic/person_0_0.csv", base_path), index, peers);
 
@@ -69,48 +74,40 @@ pub fn load_forum(base_path: &str, index: usize, peers: usize) -> Vec<Forum> {
     return result;
 }
 
-// FIXME
-#[allow(dead_code)]
+pub fn parse_post(row: Vec<String>) -> Post {
+    let mut row_iter = row.into_iter();
+    let created = parse_datetime(row_iter.next().unwrap());
+    let id = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let image = row_iter.next().unwrap().parse::<String>().unwrap();
+    let ip = row_iter.next().unwrap().parse::<String>().unwrap();
+    let browser = row_iter.next().unwrap().parse::<String>().unwrap();
+    let lang = row_iter.next().unwrap().parse::<String>().unwrap();
+    let content = row_iter.next().unwrap().parse::<String>().unwrap();
+    let length = row_iter.next().unwrap().parse().unwrap();
+    return Post::new(id, created, image, ip, browser, lang, content, length);
+}
+
 pub fn load_post(base_path: &str, index: usize, peers: usize) -> Vec<Post> {
     let data = load_data(&format!("{}dynamic/post_0_0.csv", base_path), index, peers);
 
-    let mut result = Vec::<Post>::new();
-
-    for row in data.into_iter() {
-        let mut row_iter = row.into_iter();
-        let created = parse_datetime(row_iter.next().unwrap());
-        let id = row_iter.next().unwrap().parse::<Id>().unwrap();
-        let image = row_iter.next().unwrap().parse::<String>().unwrap();
-        let ip = row_iter.next().unwrap().parse::<String>().unwrap();
-        let browser = row_iter.next().unwrap().parse::<String>().unwrap();
-        let lang = row_iter.next().unwrap().parse::<String>().unwrap();
-        let content = row_iter.next().unwrap().parse::<String>().unwrap();
-        let length = row_iter.next().unwrap().parse().unwrap();
-        result.push(Post::new(id, created, image, ip, browser, lang, content, length));
-    }
+    return data.into_iter().map(parse_post).collect();
+}
 
-    return result;
+pub fn parse_comment(row: Vec<String>) -> Comment {
+    let mut row_iter = row.into_iter();
+    let created = parse_datetime(row_iter.next().unwrap());
+    let id = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let ip = row_iter.next().unwrap().parse::<String>().unwrap();
+    let browser = row_iter.next().unwrap().parse::<String>().unwrap();
+    let content = row_iter.next().unwrap().parse::<String>().unwrap();
+    let length = row_iter.next().unwrap().parse().unwrap();
+    return Comment::new(id, created, ip, browser, content, length);
 }
 
-// FIXME
-#[allow(dead_code)]
 pub fn load_comment(base_path: &str, index: usize, peers: usize) -> Vec<Comment> {
     let data = load_data(&format!("{}dynamic/comment_0_0.csv", base_path), index, peers);
 
-    let mut result = Vec::<Comment>::new();
-
-    for row in data.into_iter() {
-        let mut row_iter = row.into_iter();
-        let created = parse_datetime(row_iter.next().unwrap());
-        let id = row_iter.next().unwrap().parse::<Id>().unwrap();
-        let ip = row_iter.next().unwrap().parse::<String>().unwrap();
-        let browser = row_iter.next().unwrap().parse::<String>().unwrap();
-        let content = row_iter.next().unwrap().parse::<String>().unwrap();
-        let length = row_iter.next().unwrap().parse().unwrap();
-        result.push(Comment::new(id, created, ip, browser, content, length));
-    }
-
-    return result;
+    return data.into_iter().map(parse_comment).collect();
 }
 
 pub fn load_tag(base_path: &str, index: usize, peers: usize) -> Vec<Tag> {
@@ -163,6 +160,59 @@ pub fn load_place(base_path: &str, index: usize, peers: usize)-> Vec<Place> {
     return result;
 }
 
+pub fn load_organisation(base_path: &str, index: usize, peers: usize) -> Vec<Organisation> {
+    let data = load_data(&format!("{}static/organisation_0_0.csv", base_path), index, peers);
+
+    let mut result = Vec::<Organisation>::new();
+
+    for row in data.into_iter() {
+        let mut row_iter = row.into_iter();
+        let id = row_iter.next().unwrap().parse::<Id>().unwrap();
+        let tp = row_iter.next().unwrap().parse::<String>().unwrap();
+        let name = row_iter.next().unwrap().parse::<String>().unwrap();
+        let url = row_iter.next().unwrap().parse::<String>().unwrap();
+        result.push(Organisation::new(id, tp, name, url));
+    }
+
+    return result;
+}
+
+/// Loads the places of organisations, as (organisation_id, place_id) connections.
+/// Universities are located in cities, companies in countries.
+pub fn load_organisation_is_located_in(base_path: &str, index: usize, peers: usize) -> Vec<Connection> {
+    return load_connection("static/organisation_isLocatedIn_place_0_0.csv", base_path, index, peers);
+}
+
+pub fn parse_study_at(row: Vec<String>) -> StudyAt {
+    let mut row_iter = row.into_iter();
+    let created = parse_datetime(row_iter.next().unwrap());
+    let person = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let university = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let class_year = row_iter.next().unwrap().parse::<i32>().unwrap();
+    return StudyAt::new(created, person, university, class_year);
+}
+
+pub fn load_study_at(base_path: &str, index: usize, peers: usize) -> Vec<StudyAt> {
+    let data = load_data(&format!("{}dynamic/person_studyAt_organisation_0_0.csv", base_path), index, peers);
+
+    return data.into_iter().map(parse_study_at).collect();
+}
+
+pub fn parse_work_at(row: Vec<String>) -> WorkAt {
+    let mut row_iter = row.into_iter();
+    let created = parse_datetime(row_iter.next().unwrap());
+    let person = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let company = row_iter.next().unwrap().parse::<Id>().unwrap();
+    let work_from = row_iter.next().unwrap().parse::<i32>().unwrap();
+    return WorkAt::new(created, person, company, work_from);
+}
+
+pub fn load_work_at(base_path: &str, index: usize, peers: usize) -> Vec<WorkAt> {
+    let data = load_data(&format!("{}dynamic/person_workAt_organisation_0_0.csv", base_path), index, peers);
+
+    return data.into_iter().map(parse_work_at).collect();
+}
+
 pub fn load_connection(filename: &str, base_path: &str, index: usize, peers: usize) -> Vec<Connection> {
     let data = load_data(&format!("{}{}", base_path, filename), index, peers);
 
@@ -195,6 +245,36 @@ pub fn load_dynamic_connection(filename: &str, base_path: &str, index: usize, pe
     return result;
 }
 
+/// Loads forum memberships, where `created` is the date the person joined the forum.
+pub fn load_forum_has_member(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/forum_hasMember_person_0_0.csv", base_path, index, peers);
+}
+
+/// Loads the Tags persons are interested in, as (person_id, tag_id) connections.
+pub fn load_person_has_interest(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/person_hasInterest_tag_0_0.csv", base_path, index, peers);
+}
+
+/// Loads the likes of posts, as (person_id, post_id) connections.
+pub fn load_person_likes_post(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/person_likes_post_0_0.csv", base_path, index, peers);
+}
+
+/// Loads the likes of comments, as (person_id, comment_id) connections.
+pub fn load_person_likes_comment(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/person_likes_comment_0_0.csv", base_path, index, peers);
+}
+
+/// Loads the places posts were sent from, as (post_id, place_id) connections.
+pub fn load_post_is_located_in(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/post_isLocatedIn_place_0_0.csv", base_path, index, peers);
+}
+
+/// Loads the places comments were sent from, as (comment_id, place_id) connections.
+pub fn load_comment_is_located_in(base_path: &str, index: usize, peers: usize) -> Vec<DynamicConnection> {
+    return load_dynamic_connection("dynamic/comment_isLocatedIn_place_0_0.csv", base_path, index, peers);
+}
+
 #[allow(dead_code)]
 pub fn load_bi_param(base_path: &str, bi_number: usize) -> Vec<Vec<String>> {
     return load_data(&format!("{}substitution_parameters/bi_{}_param.txt", base_path, bi_number), 0, 1);
@@ -1,3 +1,4 @@
 pub mod loader;
 pub mod types;
 pub mod helpers;
+pub mod delta_query;
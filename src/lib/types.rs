@@ -7,13 +7,13 @@ named_tuple!(
     #[derive(Clone, Debug, Default, Hash, PartialEq, PartialOrd, Eq)]
     pub struct Person {
         pub id: Id,
-        created: Date,
+        pub created: Date,
         pub first_name: String,
         pub last_name: String,
-        gender: String,
-        birthday: String,
-        ip: String,
-        browser: String
+        pub gender: String,
+        pub birthday: String,
+        pub ip: String,
+        pub browser: String
     }
 );
 
@@ -31,12 +31,12 @@ named_tuple!(
     pub struct Post {
         pub id: Id,
         pub created: Date,
-        image: String,
+        pub image: String,
         ip: String,
         browser: String,
-        lang: String,
-        content: String,
-        length: usize,
+        pub lang: String,
+        pub content: String,
+        pub length: usize,
     }
 );
 
@@ -44,11 +44,11 @@ named_tuple!(
     #[derive(Clone, Debug, Default, Hash, PartialEq, PartialOrd, Eq)]
     pub struct Comment {
         pub id: Id,
-        created: Date,
+        pub created: Date,
         ip: String,
         browser: String,
-        content: String,
-        length: usize,
+        pub content: String,
+        pub length: usize,
     }
 );
 
@@ -89,6 +89,36 @@ named_tuple!(
 );
 
 
+named_tuple!(
+    #[derive(Clone, Debug, Default, Hash, PartialEq, PartialOrd, Eq)]
+    pub struct Organisation {
+        pub id: Id,
+        pub type_: String,
+        pub name: String,
+        url: String
+    }
+);
+
+named_tuple!(
+    #[derive(Clone, Debug, Default, Hash, PartialEq, PartialOrd, Eq)]
+    pub struct StudyAt {
+        pub created: Date,
+        pub person: Id,
+        pub university: Id,
+        pub class_year: i32,
+    }
+);
+
+named_tuple!(
+    #[derive(Clone, Debug, Default, Hash, PartialEq, PartialOrd, Eq)]
+    pub struct WorkAt {
+        pub created: Date,
+        pub person: Id,
+        pub company: Id,
+        pub work_from: i32,
+    }
+);
+
 pub type TagClass = Tag;
 
 // todo remove duplicate implementations?
@@ -139,3 +169,21 @@ impl Ord for Comment {
         return self.field_values().cmp(&other.field_values());
     }
 }
+
+impl Ord for Organisation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.field_values().cmp(&other.field_values());
+    }
+}
+
+impl Ord for StudyAt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.field_values().cmp(&other.field_values());
+    }
+}
+
+impl Ord for WorkAt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.field_values().cmp(&other.field_values());
+    }
+}
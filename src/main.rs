@@ -22,12 +22,48 @@ fn main() {
     // let params = lib::loader::load_bi_param(path.as_str(), query_id);
 
     let runner = match query_id {
+        1 => queries::q1::run,
+        2 => queries::q2::run,
         3 => queries::q3::run,
+        4 => queries::q4::run,
         5 => queries::q5::run,
+        6 => queries::q6::run,
         7 => queries::q7::run,
+        8 => queries::q8::run,
+        10 => queries::q10::run,
+        11 => queries::q11::run,
+        12 => queries::q12::run,
+        13 => queries::q13::run,
+        14 => queries::q14::run,
+        109 => queries::q109::run, // 109, because it was dropped in newer LDBC specs.
+        111 => queries::q111::run, // 111, because it was dropped in newer LDBC specs.
+        112 => queries::q112::run, // 112, because it was dropped in newer LDBC specs.
         114 => queries::q114::run, // 114, because it was dropped in newer LDBC specs.
+        115 => queries::q115::run, // 115, because it was dropped in newer LDBC specs.
+        120 => queries::q120::run, // 120, because it was dropped in newer LDBC specs.
+        123 => queries::q123::run, // 123, because it was dropped in newer LDBC specs.
+        124 => queries::q124::run, // 124, because it was dropped in newer LDBC specs.
         15 => queries::q15::run,
+        315 => queries::q15w::run, // 315, because 15 still runs the trusted connection paths query.
+        16 => queries::q16::run,
+        17 => queries::q17::run,
+        18 => queries::q18::run,
         19 => queries::q19::run,
+        20 => queries::q20::run,
+        201 => queries::ic1::run, // 201, because it is Interactive complex query 1.
+        202 => queries::ic2::run, // 202, because it is Interactive complex query 2.
+        203 => queries::ic3::run, // 203, because it is Interactive complex query 3.
+        204 => queries::ic4::run, // 204, because it is Interactive complex query 4.
+        205 => queries::ic5::run, // 205, because it is Interactive complex query 5.
+        206 => queries::ic6::run, // 206, because it is Interactive complex query 6.
+        207 => queries::ic7::run, // 207, because it is Interactive complex query 7.
+        208 => queries::ic8::run, // 208, because it is Interactive complex query 8.
+        209 => queries::ic9::run, // 209, because it is Interactive complex query 9.
+        210 => queries::ic10::run, // 210, because it is Interactive complex query 10.
+        211 => queries::ic11::run, // 211, because it is Interactive complex query 11.
+        212 => queries::ic12::run, // 212, because it is Interactive complex query 12.
+        213 => queries::ic13::run, // 213, because it is Interactive complex query 13.
+        214 => queries::ic14::run, // 214, because it is Interactive complex query 14.
         _ => panic!("Query {} is not yet implemented.", query_id)
     };
 
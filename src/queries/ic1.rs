@@ -0,0 +1,292 @@
+/*
+LDBC SNB Interactive complex read query 1. Transitive friends with certain name
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, format_timestamp, bounded_reachability, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_first_name_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_first_name = param_first_name_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut organisation_input,
+            mut organisation_located_in_input,
+            mut study_at_input,
+            mut work_at_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (organisation_input, organisation) = scope.new_collection::<Organisation, _>();
+            let (organisation_located_in_input, organisation_located_in) = scope.new_collection::<Connection, _>();
+            let (study_at_input, study_at) = scope.new_collection::<StudyAt, _>();
+            let (work_at_input, work_at) = scope.new_collection::<WorkAt, _>();
+
+            let place_names = place
+                .map(|place| (place.id().clone(), place.name().clone()))
+                ;
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // the start person is the only root of the search
+            let root = bi_knows
+                .filter(move |(person, _friend)| param_person.eq(person))
+                .map(|(person, _friend)| person)
+                .distinct()
+                ;
+
+            // persons with the given first name, at most 3 steps away from the start person
+            let friends = bounded_reachability(&bi_knows, &root, 1, 3)
+                .map(|((_root, person_id), distance)| (person_id, distance))
+                .join_map(
+                    &person
+                        .filter(move |person| param_first_name.eq(person.first_name()))
+                        .map(|person| (
+                            person.id().clone(),
+                            (
+                                person.last_name().clone(), person.birthday().clone(), person.created().clone(),
+                                person.gender().clone(), person.browser().clone(), person.ip().clone()
+                            )
+                        )),
+                    |person_id, &distance, attributes| (person_id.clone(), (distance, attributes.clone()))
+                )
+                ; // -> (person_id, (distance, (last_name, birthday, created, gender, browser, ip)))
+
+            // the city each person lives in
+            let cities = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .join_map(&place_names, |_place_id, person_id, name| (person_id.clone(), name.clone()))
+                ; // -> (person_id, city_name)
+
+            // organisations with their name and the name of their place
+            let organisations = organisation
+                .map(|organisation| (organisation.id().clone(), organisation.name().clone()))
+                .join_map(
+                    &organisation_located_in.map(|conn| (conn.a().clone(), conn.b().clone())), // -> organisation_id, place_id
+                    |organisation_id, name, place_id| (place_id.clone(), (organisation_id.clone(), name.clone()))
+                )
+                .join_map(
+                    &place_names,
+                    |_place_id, (organisation_id, name), place_name| (organisation_id.clone(), (name.clone(), place_name.clone()))
+                )
+                ; // -> (organisation_id, (name, place_name))
+
+            // universities as "name,classYear,city" and companies as "name,workFrom,country",
+            // separated by ';' for each person
+            let universities = study_at
+                .map(|study_at| (study_at.university().clone(), (study_at.person().clone(), study_at.class_year().clone())))
+                .join_map(
+                    &organisations,
+                    |_university_id, &(person_id, class_year), (name, city)| (person_id, format!("{},{},{}", name, class_year, city))
+                )
+                .reduce(|_person_id, input, output| {
+                    let list: Vec<String> = input.iter().map(|(university, _diff)| (*university).clone()).collect();
+                    output.push((list.join(";"), 1));
+                })
+                ; // -> (person_id, universities)
+            let companies = work_at
+                .map(|work_at| (work_at.company().clone(), (work_at.person().clone(), work_at.work_from().clone())))
+                .join_map(
+                    &organisations,
+                    |_company_id, &(person_id, work_from), (name, country)| (person_id, format!("{},{},{}", name, work_from, country))
+                )
+                .reduce(|_person_id, input, output| {
+                    let list: Vec<String> = input.iter().map(|(company, _diff)| (*company).clone()).collect();
+                    output.push((list.join(";"), 1));
+                })
+                ; // -> (person_id, companies)
+
+            let result = left_outer_join(
+                &left_outer_join(
+                    &friends.join(&cities),
+                    &universities
+                ),
+                &companies
+            )
+                .map(|(person_id, ((((distance, attributes), city), universities), companies))| {
+                    let (last_name, birthday, created, gender, browser, ip) = attributes;
+                    (
+                        (distance, last_name.clone(), person_id), // sort: +distance, +last_name, +person_id
+                        vec![
+                            person_id.to_string(), last_name, distance.to_string(), birthday,
+                            format_timestamp(created as u64), gender, browser, ip,
+                            city, universities.unwrap_or_default(), companies.unwrap_or_default()
+                        ]
+                    )
+                })
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, located_in_input, place_input,
+                organisation_input, organisation_located_in_input, study_at_input, work_at_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(load_organisation(path.as_str(), index, peers), &mut organisation_input, next_time);
+        input_insert_vec(load_organisation_is_located_in(path.as_str(), index, peers), &mut organisation_located_in_input, next_time);
+        input_insert_vec(load_study_at(path.as_str(), index, peers), &mut study_at_input, next_time);
+        input_insert_vec(load_work_at(path.as_str(), index, peers), &mut work_at_input, next_time);
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "person-studyat-organisation" => {
+                    let study_at = parse_study_at(change_row);
+                    if create {
+                        study_at_input.insert(study_at);
+                    } else {
+                        study_at_input.remove(study_at);
+                    }
+                },
+                "person-workat-organisation" => {
+                    let work_at = parse_work_at(change_row);
+                    if create {
+                        work_at_input.insert(work_at);
+                    } else {
+                        work_at_input.remove(work_at);
+                    }
+                },
+                "person-knows-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        organisation_input.advance_to(next_time);
+        organisation_input.flush();
+        organisation_located_in_input.advance_to(next_time);
+        organisation_located_in_input.flush();
+        study_at_input.advance_to(next_time);
+        study_at_input.flush();
+        work_at_input.advance_to(next_time);
+        work_at_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
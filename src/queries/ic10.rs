@@ -0,0 +1,271 @@
+/*
+LDBC SNB Interactive complex read query 10. Friend recommendation
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, two_hop_friends, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+use chrono::Datelike;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_month = params[1].parse::<u32>().unwrap();
+    let next_month = param_month % 12 + 1;
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut has_interest_input,
+            mut has_creator_input,
+            mut has_tag_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (has_interest_input, has_interest) = scope.new_collection::<DynamicConnection, _>();
+            // creators for posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // tags for posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+
+            // friends of friends, who are not friends of the start person, and were born
+            // between the 21st of the given month and the 22nd of the following month
+            let candidates = two_hop_friends(&knows, param_person)
+                .filter(|(_person_id, distance)| *distance == 2)
+                .join_map(
+                    &person
+                        .map(|person| (
+                            person.id().clone(),
+                            (person.first_name().clone(), person.last_name().clone(), person.gender().clone()),
+                            parse_date(person.birthday().clone())
+                        ))
+                        .filter(move |(_person_id, _attributes, birthday)|
+                            (birthday.month() == param_month && birthday.day() >= 21)
+                                || (birthday.month() == next_month && birthday.day() < 22)
+                        )
+                        .map(|(person_id, attributes, _birthday)| (person_id, attributes)),
+                    |person_id, _distance, attributes| (person_id.clone(), attributes.clone())
+                )
+                ; // -> (person_id, (first_name, last_name, gender))
+
+            // posts with a Tag the start person is interested in
+            let common_posts = has_tag
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, post_id
+                .semijoin(
+                    &has_interest
+                        .filter(move |conn| param_person.eq(conn.a()))
+                        .map(|conn| conn.b().clone())
+                )
+                .map(|(_tag_id, post_id)| post_id)
+                .distinct()
+                .map(|post_id| (post_id, ()))
+                ;
+
+            // similarity is the number of common posts minus the number of other posts
+            let similarity = left_outer_join(
+                &has_creator
+                    .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, post_id
+                    .semijoin(&candidates.map(|(person_id, _attributes)| person_id))
+                    .map(|(person_id, post_id)| (post_id, person_id)),
+                &common_posts
+            )
+                .map(|(_post_id, (person_id, common))| (person_id, common.is_some()))
+                .reduce(|_person_id, input, output| {
+                    let mut similarity: isize = 0;
+                    for (common, diff) in input.iter() {
+                        similarity += if **common { *diff } else { -diff };
+                    }
+                    output.push((similarity, 1));
+                })
+                ; // -> (person_id, similarity)
+
+            // the city each person lives in
+            let cities = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .join_map(
+                    &place.map(|place| (place.id().clone(), place.name().clone())),
+                    |_place_id, person_id, name| (person_id.clone(), name.clone())
+                )
+                ; // -> (person_id, city_name)
+
+            let result = left_outer_join(&candidates, &similarity)
+                .join(&cities)
+                .map(|(person_id, (((first_name, last_name, gender), similarity), city))| {
+                    let similarity = similarity.unwrap_or(0);
+                    (
+                        (-similarity, person_id), // sort: -similarity, +person_id
+                        vec![
+                            person_id.to_string(), first_name, last_name,
+                            similarity.to_string(), gender, city
+                        ]
+                    )
+                })
+                ;
+
+            let arrangement = limit(&result, 10)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, located_in_input, place_input,
+                has_interest_input, has_creator_input, has_tag_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(load_person_has_interest(path.as_str(), index, peers), &mut has_interest_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                "person-hasinterest-tag" => {
+                    if create {
+                        has_interest_input.insert(d);
+                    } else {
+                        has_interest_input.remove(d);
+                    }
+                },
+                "post-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "post-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        has_interest_input.advance_to(next_time);
+        has_interest_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
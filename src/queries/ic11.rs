@@ -0,0 +1,197 @@
+/*
+LDBC SNB Interactive complex read query 11. Job referral
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, two_hop_friends};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::Join;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_country_ = params[1].clone();
+    let param_year = params[2].parse::<i32>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut place_input,
+            mut organisation_input,
+            mut organisation_located_in_input,
+            mut work_at_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (organisation_input, organisation) = scope.new_collection::<Organisation, _>();
+            let (organisation_located_in_input, organisation_located_in) = scope.new_collection::<Connection, _>();
+            let (work_at_input, work_at) = scope.new_collection::<WorkAt, _>();
+
+            // companies located in the given country
+            let companies = organisation_located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, organisation_id
+                .semijoin(
+                    &place
+                        .filter(move |place| param_country.eq(place.name()))
+                        .map(|place| place.id().clone())
+                )
+                .map(|(_place_id, organisation_id)| (organisation_id, ()))
+                .join_map(
+                    &organisation.map(|organisation| (organisation.id().clone(), organisation.name().clone())),
+                    |organisation_id, _dummy, name| (organisation_id.clone(), name.clone())
+                )
+                ; // -> (organisation_id, name)
+
+            // friends and friends of friends, who started working at these companies before the given year
+            let result = work_at
+                .filter(move |work_at| *work_at.work_from() < param_year)
+                .map(|work_at| (work_at.person().clone(), (work_at.company().clone(), work_at.work_from().clone())))
+                .semijoin(&two_hop_friends(&knows, param_person).map(|(person_id, _distance)| person_id))
+                .map(|(person_id, (company_id, work_from))| (company_id, (person_id, work_from)))
+                .join_map(&companies, |_company_id, &(person_id, work_from), name| (person_id, (work_from, name.clone())))
+                .join_map(
+                    &person.map(|person| (person.id().clone(), (person.first_name().clone(), person.last_name().clone()))),
+                    |person_id, (work_from, name), (first_name, last_name)| {
+                        // inverted bytes, followed by the maximal byte, order the names descending
+                        let name_desc: Vec<u8> = name.bytes().map(|b| std::u8::MAX - b).chain(Some(std::u8::MAX)).collect();
+                        (
+                            (work_from.clone(), person_id.clone(), name_desc), // sort: +work_from, +person_id, -name
+                            vec![
+                                person_id.to_string(), first_name.to_string(), last_name.to_string(),
+                                name.to_string(), work_from.to_string()
+                            ]
+                        )
+                    }
+                )
+                ;
+
+            let arrangement = limit(&result, 10)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, place_input,
+                organisation_input, organisation_located_in_input, work_at_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(load_organisation(path.as_str(), index, peers), &mut organisation_input, next_time);
+        input_insert_vec(load_organisation_is_located_in(path.as_str(), index, peers), &mut organisation_located_in_input, next_time);
+        input_insert_vec(load_work_at(path.as_str(), index, peers), &mut work_at_input, next_time);
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "person-workat-organisation" => {
+                    let work_at = parse_work_at(change_row);
+                    if create {
+                        work_at_input.insert(work_at);
+                    } else {
+                        work_at_input.remove(work_at);
+                    }
+                },
+                "person-knows-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        organisation_input.advance_to(next_time);
+        organisation_input.flush();
+        organisation_located_in_input.advance_to(next_time);
+        organisation_located_in_input.flush();
+        work_at_input.advance_to(next_time);
+        work_at_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
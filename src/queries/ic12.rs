@@ -0,0 +1,282 @@
+/*
+LDBC SNB Interactive complex read query 12. Expert search
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, tag_class_descendants};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_tag_class_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag_class = param_tag_class_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut tag_input,
+            mut tag_classes_input,
+            mut tag_class_is_subclass_of_input,
+            mut tag_hastype_tagclass_input,
+            mut has_tag_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (tag_classes_input, tag_classes) = scope.new_collection::<TagClass, _>();
+            let (tag_class_is_subclass_of_input, tag_class_is_subclass_of) = scope.new_collection::<Connection, _>();
+            let (tag_hastype_tagclass_input, tag_hastype_tagclass) = scope.new_collection::<Connection, _>();
+            // tags for posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments, replies to other comments are dropped by the join with the Tags of posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // friends of the start person
+            let friends = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                .filter(move |(person_id, _friend_id)| param_person.eq(person_id))
+                .map(|(_person_id, friend_id)| friend_id)
+                ;
+
+            // Tags of the given TagClass, directly or through any of its subclasses
+            let class_tags = tag_class_descendants(&tag_classes, &tag_class_is_subclass_of)
+                .semijoin(
+                    &tag_classes
+                        .filter(move |x| param_tag_class.eq(x.name()))
+                        .map(|x| x.id().clone())
+                )
+                .map(|(_tag_class_id, descendant_id)| (descendant_id, ()))
+                .join_map(
+                    &tag_hastype_tagclass.map(|conn| (conn.b().clone(), conn.a().clone())), // -> tag_class_id, tag_id
+                    |_descendant_id, _dummy, tag_id| (tag_id.clone(), ())
+                )
+                .join_map(&tag.map(|tag| (tag.id().clone(), tag.name().clone())), |tag_id, _dummy, name| (tag_id.clone(), name.clone()))
+                ; // -> (tag_id, name)
+
+            // direct replies of the friends to posts with any of these Tags
+            let replies = has_creator
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, comment_id
+                .semijoin(&friends)
+                .map(|(person_id, comment_id)| (comment_id, person_id))
+                .join_map(
+                    &reply_of.map(|conn| (conn.a().clone(), conn.b().clone())), // -> comment_id, parent_id
+                    |comment_id, person_id, parent_id| (parent_id.clone(), (person_id.clone(), comment_id.clone()))
+                )
+                .join_map(
+                    &has_tag
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, post_id
+                        .join_map(&class_tags, |_tag_id, post_id, name| (post_id.clone(), name.clone())),
+                    |_post_id, &(person_id, comment_id), name| (person_id, (comment_id, name.clone()))
+                )
+                ; // -> (person_id, (comment_id, tag_name))
+
+            // count the replies and collect the Tag names of the replied posts, separated by ';'
+            let reply_counts = replies
+                .map(|(person_id, (comment_id, _name))| (person_id, comment_id))
+                .distinct()
+                .map(|(person_id, _comment_id)| person_id)
+                .count()
+                ; // -> (person_id, reply_count)
+            let tag_names = replies
+                .map(|(person_id, (_comment_id, name))| (person_id, name))
+                .distinct()
+                .reduce(|_person_id, input, output| {
+                    let list: Vec<String> = input.iter().map(|(name, _diff)| (*name).clone()).collect();
+                    output.push((list.join(";"), 1));
+                })
+                ; // -> (person_id, tag_names)
+
+            let result = reply_counts
+                .join(&tag_names)
+                .join_map(
+                    &person.map(|person| (person.id().clone(), (person.first_name().clone(), person.last_name().clone()))),
+                    |person_id, &(count, ref names), (first_name, last_name)| (
+                        (std::isize::MAX - count, person_id.clone()), // sort: -count, +person_id
+                        vec![
+                            person_id.to_string(), first_name.to_string(), last_name.to_string(),
+                            names.to_string(), count.to_string()
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, tag_input, tag_classes_input, tag_class_is_subclass_of_input,
+                tag_hastype_tagclass_input, has_tag_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_tag_class(path.as_str(), index, peers), &mut tag_classes_input, next_time);
+        input_insert_vec(
+            load_connection("static/tagclass_isSubclassOf_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_class_is_subclass_of_input,
+            next_time
+        );
+        input_insert_vec(
+            load_connection("static/tag_hasType_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_hastype_tagclass_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "post-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                "comment-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        tag_classes_input.advance_to(next_time);
+        tag_classes_input.flush();
+        tag_class_is_subclass_of_input.advance_to(next_time);
+        tag_class_is_subclass_of_input.flush();
+        tag_hastype_tagclass_input.advance_to(next_time);
+        tag_hastype_tagclass_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,142 @@
+/*
+LDBC SNB Interactive complex read query 13. Single shortest path
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, left_outer_join, shortest_path_lengths};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person_a = params[0].parse::<Id>().unwrap();
+    let param_person_b = params[1].parse::<Id>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut query_input,
+            mut knows_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (query_input, query) = scope.new_collection::<(Id, Id), _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // the length is -1 if the persons cannot reach each other
+            let result = left_outer_join(&query.map(|goal| (goal, ())), &shortest_path_lengths(&bi_knows, &query))
+                .map(|(_goal, (_dummy, length))| {
+                    let length = length.map(|length| length as i64).unwrap_or(-1);
+                    ((), vec![length.to_string()])
+                })
+                ;
+
+            let arrangement = limit(&result, 1)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                query_input, knows_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(vec![(param_person_a, param_person_b)], &mut query_input, next_time);
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        query_input.advance_to(next_time);
+        query_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,252 @@
+/*
+LDBC SNB Interactive complex read query 14. Trusted connection paths
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, shortest_paths, enumerate_shortest_paths};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person_a = params[0].parse::<Id>().unwrap();
+    let param_person_b = params[1].parse::<Id>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut query_input,
+            mut knows_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (query_input, query) = scope.new_collection::<(Id, Id), _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            // hasCreator for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // all the shortest paths, along the edges found by the bidirectional search
+            let paths = enumerate_shortest_paths(&query, &shortest_paths(&bi_knows, &query))
+                .map(|(_goal, path)| path)
+                ;
+
+            // edges of the paths, ordered by their ids
+            let path_edges = paths
+                .flat_map(|path| {
+                    let edges: Vec<_> = path.windows(2).map(|edge| (edge[0].min(edge[1]), edge[0].max(edge[1]))).collect();
+                    edges.into_iter().map(move |edge| (edge, path.clone()))
+                })
+                ; // -> (edge, path)
+
+            // replies between the two persons of each edge, a reply to a post is worth
+            // two points and a reply to a comment is worth one point
+            let creators = has_creator
+                .map(|conn| (conn.a().clone(), conn.b().clone()))
+                ; // -> (message_id, person_id)
+            // messages that reply to another message are comments, all others are posts
+            let comment_ids = reply_of
+                .map(|conn| conn.a().clone())
+                .distinct()
+                ;
+            let replies = creators
+                .join_map(
+                    &reply_of.map(|conn| (conn.a().clone(), conn.b().clone())), // -> comment_id, parent_id
+                    |_comment_id, person_id, parent_id| (parent_id.clone(), person_id.clone())
+                )
+                ; // -> (parent_id, person_id)
+            let parent_creators = creators
+                .antijoin(&comment_ids)
+                .map(|(post_id, person_id)| (post_id, (person_id, true)))
+                .concat(
+                    &creators
+                        .semijoin(&comment_ids)
+                        .map(|(comment_id, person_id)| (comment_id, (person_id, false)))
+                )
+                ; // -> (message_id, (person_id, is_post))
+            let edge_scores = replies
+                .join_map(&parent_creators, |_parent_id, &person_a, &(person_b, is_post)|
+                    ((person_a.min(person_b), person_a.max(person_b)), is_post)
+                )
+                .semijoin(&path_edges.map(|(edge, _path)| edge).distinct())
+                .flat_map(|(edge, is_post)| if is_post { vec![edge, edge] } else { vec![edge] })
+                .count()
+                ; // -> (edge, score), where the score is counted in half points
+
+            // the weight of a path is the sum of the scores of its edges
+            let weighted_paths = path_edges
+                .join_map(&edge_scores, |_edge, path, &score| (path.clone(), score))
+                .concat(&paths.map(|path| (path, 0)))
+                .reduce(|_path, input, output| {
+                    let mut sum: isize = 0;
+                    for (score, diff) in input.iter() {
+                        sum += **score * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                ; // -> (path, score)
+
+            let result = weighted_paths
+                .map(|(path, score)| (
+                    (std::isize::MAX - score, path.clone()), // sort: -score, +path
+                    vec![
+                        path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(";"),
+                        (score as f64 / 2.0).to_string()
+                    ]
+                ))
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                query_input, knows_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        // insert hasCreator relations both for posts and comments, to handle them together in the dataflow
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations both for posts and comments, to handle them together in the dataflow
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+        input_insert_vec(vec![(param_person_a, param_person_b)], &mut query_input, next_time);
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        query_input.advance_to(next_time);
+        query_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,221 @@
+/*
+LDBC SNB Interactive complex read query 2. Recent messages by your friends
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, format_timestamp, message_contents};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::Join;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_max_date_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_max_date = parse_datetime(param_max_date_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut post_input,
+            mut comment_input,
+            mut has_creator_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (post_input, post) = scope.new_collection::<Post, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+
+            // friends of the start person
+            let friends = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                .filter(move |(person_id, _friend_id)| param_person.eq(person_id))
+                .map(|(_person_id, friend_id)| friend_id)
+                ; // -> friend_id
+
+            // messages of the friends created before the given date
+            let messages = has_creator
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, message_id
+                .semijoin(&friends)
+                .map(|(creator_id, message_id)| (message_id, creator_id))
+                .join_map(
+                    &message_contents(&post, &comment)
+                        .filter(move |(_message_id, (created, _content))| *created < param_max_date),
+                    |message_id, &creator_id, (created, content)|
+                        (creator_id, (std::i64::MAX - created, message_id.clone(), content.clone()))
+                )
+                ; // -> (creator_id, (-created, message_id, content))
+
+            let result = messages
+                .join_map(
+                    &person.map(|person| (person.id().clone(), (person.first_name().clone(), person.last_name().clone()))),
+                    |creator_id, (created_desc, message_id, content), (first_name, last_name)| (
+                        (created_desc.clone(), message_id.clone()), // sort: -created, +message_id
+                        vec![
+                            creator_id.to_string(), first_name.to_string(), last_name.to_string(),
+                            message_id.to_string(), content.to_string(),
+                            format_timestamp((std::i64::MAX - created_desc) as u64)
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, post_input, comment_input, has_creator_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(load_post(path.as_str(), index, peers), &mut post_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "post" => {
+                    let post = parse_post(change_row);
+                    if create {
+                        post_input.insert(post);
+                    } else {
+                        post_input.remove(post);
+                    }
+                },
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "message-hascreator-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "person-knows-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        post_input.advance_to(next_time);
+        post_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
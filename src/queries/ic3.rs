@@ -0,0 +1,280 @@
+/*
+LDBC SNB Interactive complex read query 3. Friends and friends of friends that have been to given countries
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, containing_places, two_hop_friends, DAY};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Join, Reduce};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_country_x_ = params[1].clone();
+    let param_country_y_ = params[2].clone();
+    let param_start_ = params[3].clone();
+    let param_duration = params[4].parse::<Date>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country_x = param_country_x_.clone();
+        let param_country_y = param_country_y_.clone();
+        let param_start = parse_datetime(param_start_.clone());
+        let param_end = param_start + param_duration * DAY;
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut has_creator_input,
+            mut message_located_in_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // locations for comments AND posts
+            let (message_located_in_input, message_located_in) = scope.new_collection::<DynamicConnection, _>();
+
+            // the given countries, where country x is marked with true
+            let countries = place
+                .filter(move |place| param_country_x.eq(place.name()))
+                .map(|place| (place.id().clone(), true))
+                .concat(
+                    &place
+                        .filter(move |place| param_country_y.eq(place.name()))
+                        .map(|place| (place.id().clone(), false))
+                )
+                ; // -> (country_id, is_x)
+            let place_countries = containing_places(&place, &place_is_part_of_place, "country".to_string())
+                .map(|(place_id, country_id)| (country_id, place_id))
+                .join_map(&countries, |_country_id, place_id, is_x| (place_id.clone(), is_x.clone()))
+                ; // -> (place_id, is_x) for the places in the given countries
+
+            // friends and friends of friends, who do not live in any of the given countries
+            let friends = two_hop_friends(&knows, param_person)
+                .map(|(person_id, _distance)| (person_id, ()))
+                .antijoin(
+                    &located_in
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                        .semijoin(&place_countries.map(|(place_id, _is_x)| place_id))
+                        .map(|(_place_id, person_id)| person_id)
+                )
+                .map(|(person_id, _dummy)| person_id)
+                ;
+
+            // messages of these persons sent from the given countries in the given time frame.
+            // isLocatedIn relations are created at the same time as the message itself.
+            let counts = message_located_in
+                .filter(move |conn| param_start <= *conn.created() && *conn.created() < param_end)
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, message_id
+                .join_map(&place_countries, |_place_id, message_id, is_x| (message_id.clone(), is_x.clone()))
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |_message_id, is_x, person_id| (person_id.clone(), is_x.clone())
+                )
+                .semijoin(&friends)
+                .reduce(|_person_id, input, output| {
+                    let mut x_count: isize = 0;
+                    let mut y_count: isize = 0;
+                    for (is_x, diff) in input.iter() {
+                        if **is_x {
+                            x_count += diff;
+                        } else {
+                            y_count += diff;
+                        }
+                    }
+                    output.push(((x_count, y_count), 1));
+                })
+                .filter(|(_person_id, (x_count, y_count))| *x_count > 0 && *y_count > 0)
+                ; // -> (person_id, (x_count, y_count))
+
+            let result = counts
+                .join_map(
+                    &person.map(|person| (person.id().clone(), (person.first_name().clone(), person.last_name().clone()))),
+                    |person_id, &(x_count, y_count), (first_name, last_name)| (
+                        (std::isize::MAX - x_count, person_id.clone()), // sort: -x_count, +person_id
+                        vec![
+                            person_id.to_string(), first_name.to_string(), last_name.to_string(),
+                            x_count.to_string(), y_count.to_string(), (x_count + y_count).to_string()
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, knows_input, located_in_input, place_input, place_is_part_of_place_input,
+                has_creator_input, message_located_in_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert isLocatedIn relations of messages
+        input_insert_vec(
+            load_post_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_comment_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "message-islocatedin-place" => {
+                    if create {
+                        message_located_in_input.insert(d);
+                    } else {
+                        message_located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        message_located_in_input.advance_to(next_time);
+        message_located_in_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,207 @@
+/*
+LDBC SNB Interactive complex read query 4. New topics
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, two_hop_friends, DAY};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_start_ = params[1].clone();
+    let param_duration = params[2].parse::<Date>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_start = parse_datetime(param_start_.clone());
+        let param_end = param_start + param_duration * DAY;
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut knows_input,
+            mut has_creator_input,
+            mut has_tag_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            // creators for posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // tags for posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+
+            // direct friends of the start person
+            let friends = two_hop_friends(&knows, param_person)
+                .filter(|(_person_id, distance)| *distance == 1)
+                .map(|(person_id, _distance)| person_id)
+                ;
+
+            // Tags of the posts of the friends, with the creation date of the post.
+            // hasTag relations are created at the same time as the post itself.
+            let friend_post_tags = has_tag
+                .map(|conn| (conn.a().clone(), (conn.b().clone(), conn.created().clone()))) // -> post_id, (tag_id, created)
+                .semijoin(
+                    &has_creator
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, post_id
+                        .semijoin(&friends)
+                        .map(|(_person_id, post_id)| post_id)
+                )
+                ; // -> (post_id, (tag_id, created))
+
+            // Tags which were already used by friends before the time frame
+            let old_tags = friend_post_tags
+                .filter(move |(_post_id, (_tag_id, created))| *created < param_start)
+                .map(|(_post_id, (tag_id, _created))| tag_id)
+                .distinct()
+                ;
+
+            // count the posts in the time frame for each new Tag
+            let result = friend_post_tags
+                .filter(move |(_post_id, (_tag_id, created))| param_start <= *created && *created < param_end)
+                .map(|(_post_id, (tag_id, _created))| (tag_id, ()))
+                .antijoin(&old_tags)
+                .map(|(tag_id, _dummy)| tag_id)
+                .count()
+                .join_map(
+                    &tag.map(|tag| (tag.id().clone(), tag.name().clone())),
+                    |_tag_id, &count: &isize, name| (
+                        (std::isize::MAX - count, name.clone()), // sort: -count, +name
+                        vec![name.to_string(), count.to_string()]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 10)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, knows_input, has_creator_input, has_tag_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "post-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "post-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
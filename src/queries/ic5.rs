@@ -0,0 +1,212 @@
+/*
+LDBC SNB Interactive complex read query 5. New groups
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, two_hop_friends, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_min_date_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_min_date = parse_datetime(param_min_date_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut forum_input,
+            mut knows_input,
+            mut forum_hasmember_input,
+            mut forum_containerof_post_input,
+            mut has_creator_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (forum_input, forum) = scope.new_collection::<Forum, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_hasmember_input, forum_hasmember) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_containerof_post_input, forum_containerof_post) = scope.new_collection::<DynamicConnection, _>();
+            // creators for posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+
+            // friends and friends of friends, who joined a forum after the given date
+            let new_members = forum_hasmember
+                .filter(move |conn| *conn.created() > param_min_date)
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, forum_id
+                .semijoin(&two_hop_friends(&knows, param_person).map(|(person_id, _distance)| person_id))
+                .map(|(person_id, forum_id)| (forum_id, person_id))
+                ; // -> (forum_id, person_id)
+
+            // posts of the new members in the forums they joined
+            let post_counts = forum_containerof_post
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> post_id, forum_id
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> post_id, person_id
+                    |_post_id, forum_id, person_id| ((forum_id.clone(), person_id.clone()), ())
+                )
+                .semijoin(&new_members)
+                .map(|((forum_id, _person_id), ())| forum_id)
+                .count()
+                ; // -> (forum_id, post_count)
+
+            // forums without such posts have a post count of 0
+            let result = left_outer_join(
+                &new_members.map(|(forum_id, _person_id)| forum_id).distinct().map(|forum_id| (forum_id, ())),
+                &post_counts
+            )
+                .join_map(
+                    &forum.map(|forum| (forum.id().clone(), forum.title().clone())),
+                    |forum_id, &((), count), title| {
+                        let count = count.unwrap_or(0);
+                        (
+                            (std::isize::MAX - count, forum_id.clone()), // sort: -count, +forum_id
+                            vec![title.to_string(), count.to_string()]
+                        )
+                    }
+                )
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                forum_input, knows_input, forum_hasmember_input, forum_containerof_post_input, has_creator_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_forum(path.as_str(), index, peers), &mut forum_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(load_forum_has_member(path.as_str(), index, peers), &mut forum_hasmember_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_containerOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut forum_containerof_post_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(forum_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "forum-hasmember-person" => {
+                    if create {
+                        forum_hasmember_input.insert(d);
+                    } else {
+                        forum_hasmember_input.remove(d);
+                    }
+                },
+                "forum-containerof-post" => {
+                    if create {
+                        forum_containerof_post_input.insert(d);
+                    } else {
+                        forum_containerof_post_input.remove(d);
+                    }
+                },
+                "post-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        forum_input.advance_to(next_time);
+        forum_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        forum_hasmember_input.advance_to(next_time);
+        forum_hasmember_input.flush();
+        forum_containerof_post_input.advance_to(next_time);
+        forum_containerof_post_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,207 @@
+/*
+LDBC SNB Interactive complex read query 8. Recent replies
+https://ldbcouncil.org/ldbc_snb_docs/ldbc-snb-specification.pdf, section Interactive Workload / Complex Reads
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, format_timestamp};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::Join;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut comment_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            let creators = has_creator
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> message_id, person_id
+                ;
+
+            // direct replies to the messages of the start person, with their creators
+            let replies = reply_of
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> parent_id, reply_id
+                .join_map(
+                    &creators.filter(move |(_message_id, person_id)| param_person.eq(person_id)),
+                    |_parent_id, reply_id, person_id| (reply_id.clone(), person_id.clone())
+                )
+                .join_map(&creators, |reply_id, _person_id, replier_id| (reply_id.clone(), replier_id.clone()))
+                .join_map(
+                    &comment.map(|comment| (comment.id().clone(), (comment.created().clone(), comment.content().clone()))),
+                    |reply_id, &replier_id, (created, content)|
+                        (replier_id, (std::i64::MAX - created, reply_id.clone(), content.clone()))
+                )
+                ; // -> (replier_id, (-created, reply_id, content))
+
+            let result = replies
+                .join_map(
+                    &person.map(|person| (person.id().clone(), (person.first_name().clone(), person.last_name().clone()))),
+                    |replier_id, (created_desc, reply_id, content), (first_name, last_name)| (
+                        (created_desc.clone(), reply_id.clone()), // sort: -created, +reply_id
+                        vec![
+                            replier_id.to_string(), first_name.to_string(), last_name.to_string(),
+                            format_timestamp((std::i64::MAX - created_desc) as u64),
+                            reply_id.to_string(), content.to_string()
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, comment_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "message-hascreator-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
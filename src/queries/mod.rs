@@ -1,6 +1,42 @@
+pub mod q1;
+pub mod q2;
 pub mod q3;
+pub mod q4;
 pub mod q5;
+pub mod q6;
 pub mod q7;
+pub mod q8;
+pub mod q10;
+pub mod q11;
+pub mod q12;
+pub mod q13;
+pub mod q14;
+pub mod q109;
+pub mod q111;
+pub mod q112;
 pub mod q114;
+pub mod q115;
+pub mod q120;
+pub mod q123;
+pub mod q124;
 pub mod q15;
+pub mod q15w;
+pub mod q16;
+pub mod q17;
+pub mod q18;
 pub mod q19;
+pub mod q20;
+pub mod ic1;
+pub mod ic2;
+pub mod ic3;
+pub mod ic4;
+pub mod ic5;
+pub mod ic6;
+pub mod ic7;
+pub mod ic8;
+pub mod ic9;
+pub mod ic10;
+pub mod ic11;
+pub mod ic12;
+pub mod ic13;
+pub mod ic14;
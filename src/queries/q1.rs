@@ -0,0 +1,184 @@
+/*
+LDBC SNB BI query 1. Posting summary
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-01.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Reduce};
+use timely::dataflow::ProbeHandle;
+use chrono::Datelike;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_date_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_date = parse_datetime(param_date_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut post_input,
+            mut comment_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (post_input, post) = scope.new_collection::<Post, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // collect posts and comments created before the given date
+            let messages = post
+                .map(|post| (post.created().clone(), post.length().clone(), false))
+                .concat(
+                    &comment.map(|comment| (comment.created().clone(), comment.length().clone(), true))
+                )
+                .filter(move |(created, _length, _is_comment)| *created < param_date)
+                ; // -> (created, length, is_comment)
+
+            // count messages and sum their length in each (year, is_comment, length_category) group
+            let groups = messages
+                .map(|(created, length, is_comment)| {
+                    let year = chrono::NaiveDateTime::from_timestamp(created, 0).year();
+                    let category = match length {
+                        0..=39 => 0,
+                        40..=79 => 1,
+                        80..=159 => 2,
+                        _ => 3,
+                    };
+                    ((year, is_comment, category), length)
+                })
+                .reduce(|_group, input, output| {
+                    let mut count: isize = 0;
+                    let mut sum: isize = 0;
+                    for (length, diff) in input.iter() {
+                        count += diff;
+                        sum += (**length as isize) * diff;
+                    }
+                    output.push(((count, sum), 1));
+                })
+                ; // -> ((year, is_comment, category), (count, sum))
+
+            // total number of messages, needed for the percentage column
+            let total = messages
+                .map(|_message| ())
+                .count()
+                ; // -> ((), total)
+
+            // every group is joined to the single total, so a change in the number of messages
+            // updates the percentage of all the groups
+            let result = groups
+                .map(|(group, counts)| ((), (group, counts)))
+                .join_map(
+                    &total,
+                    |_, &((year, is_comment, category), (count, sum)), &total| (
+                        (std::i32::MAX - year, is_comment, category), // sort: -year, +is_comment, +category
+                        vec![
+                            year.to_string(), is_comment.to_string(), category.to_string(), count.to_string(),
+                            (sum as f64 / count as f64).to_string(), sum.to_string(),
+                            (count as f64 / total as f64).to_string()
+                        ]
+                    )
+                )
+                ;
+
+            // there are at most 4 categories * 2 message types in each year
+            let arrangement = limit(&result, 1000)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                post_input, comment_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_post(path.as_str(), index, peers), &mut post_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(post_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "post" => {
+                    let post = parse_post(change_row);
+                    if create {
+                        post_input.insert(post);
+                    } else {
+                        post_input.remove(post);
+                    }
+                },
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        post_input.advance_to(next_time);
+        post_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,266 @@
+/*
+LDBC SNB BI query 10. Experts in social circle
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-10.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, country_cities, tag_class_tags, bounded_reachability};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person = params[0].parse::<Id>().unwrap();
+    let param_country_ = params[1].clone();
+    let param_tag_class_ = params[2].clone();
+    let param_min_hops = params[3].parse::<u32>().unwrap();
+    let param_max_hops = params[4].parse::<u32>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+        let param_tag_class = param_tag_class_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut tag_input,
+            mut tag_classes_input,
+            mut tag_hastype_tagclass_input,
+            mut has_tag_input,
+            mut has_creator_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (tag_classes_input, tag_classes) = scope.new_collection::<TagClass, _>();
+            let (tag_hastype_tagclass_input, tag_hastype_tagclass) = scope.new_collection::<Connection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // the start person is the only root of the search
+            let root = bi_knows
+                .filter(move |(person, _friend)| param_person.eq(person))
+                .map(|(person, _friend)| person)
+                .distinct()
+                ;
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // expert candidates are in the given distance from the start person, and live in the given country
+            let experts = bounded_reachability(&bi_knows, &root, param_min_hops, param_max_hops)
+                .map(|((_root, person_id), _distance)| (person_id, ()))
+                .semijoin(&people_in_country)
+                .map(|(person_id, _dummy)| person_id)
+                ;
+
+            // messages which have at least one Tag of the given TagClass
+            let class_messages = has_tag
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, message_id
+                .semijoin(&tag_class_tags(&tag_classes, &tag_hastype_tagclass, param_tag_class))
+                .map(|(_tag_id, message_id)| message_id)
+                .distinct()
+                ;
+
+            // count the messages of the experts for each Tag of the messages
+            let counts = has_creator
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, message_id
+                .semijoin(&experts)
+                .map(|(person_id, message_id)| (message_id, person_id))
+                .semijoin(&class_messages)
+                .join_map(
+                    &has_tag.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, tag_id
+                    |_message_id, person_id, tag_id| (tag_id.clone(), person_id.clone())
+                )
+                .count()
+                ; // -> ((tag_id, person_id), message_count)
+
+            let result = counts
+                .map(|((tag_id, person_id), count)| (tag_id, (person_id, count)))
+                .join_map(
+                    &tag.map(|tag| (tag.id().clone(), tag.name().clone())),
+                    |_tag_id, &(person_id, count): &(Id, isize), name| (
+                        (std::isize::MAX - count, name.clone(), person_id), // sort: -count, +name, +person_id
+                        vec![person_id.to_string(), name.to_string(), count.to_string()]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                knows_input, located_in_input, place_input, place_is_part_of_place_input,
+                tag_input, tag_classes_input, tag_hastype_tagclass_input,
+                has_tag_input, has_creator_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_tag_class(path.as_str(), index, peers), &mut tag_classes_input, next_time);
+        input_insert_vec(
+            load_connection("static/tag_hasType_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_hastype_tagclass_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        tag_classes_input.advance_to(next_time);
+        tag_classes_input.flush();
+        tag_hastype_tagclass_input.advance_to(next_time);
+        tag_hastype_tagclass_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,215 @@
+/*
+Legacy LDBC SNB BI query 9. Forum with related tags
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, tag_class_tags};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_class1_ = params[0].clone();
+    let param_tag_class2_ = params[1].clone();
+    let param_threshold = params[2].parse::<isize>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag_class1 = param_tag_class1_.clone();
+        let param_tag_class2 = param_tag_class2_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_classes_input,
+            mut tag_hastype_tagclass_input,
+            mut forum_hasmember_input,
+            mut forum_containerof_post_input,
+            mut has_tag_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_classes_input, tag_classes) = scope.new_collection::<TagClass, _>();
+            let (tag_hastype_tagclass_input, tag_hastype_tagclass) = scope.new_collection::<Connection, _>();
+            let (forum_hasmember_input, forum_hasmember) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_containerof_post_input, forum_containerof_post) = scope.new_collection::<DynamicConnection, _>();
+            // tags for posts, comments are not in forums directly
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+
+            let forum_posts = forum_containerof_post
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> post_id, forum_id
+                ;
+            let post_tags = has_tag
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, post_id
+                ;
+
+            // number of posts in each forum with at least one Tag of the given TagClass
+            let count1 = post_tags
+                .semijoin(&tag_class_tags(&tag_classes, &tag_hastype_tagclass, param_tag_class1))
+                .map(|(_tag_id, post_id)| post_id)
+                .distinct()
+                .map(|post_id| (post_id, ()))
+                .join_map(&forum_posts, |_post_id, _dummy, forum_id| forum_id.clone())
+                .count()
+                ; // -> (forum_id, count1)
+            let count2 = post_tags
+                .semijoin(&tag_class_tags(&tag_classes, &tag_hastype_tagclass, param_tag_class2))
+                .map(|(_tag_id, post_id)| post_id)
+                .distinct()
+                .map(|post_id| (post_id, ()))
+                .join_map(&forum_posts, |_post_id, _dummy, forum_id| forum_id.clone())
+                .count()
+                ; // -> (forum_id, count2)
+
+            // forums with more members than the threshold
+            let large_forums = forum_hasmember
+                .map(|conn| conn.a().clone())
+                .count()
+                .filter(move |(_forum_id, count): &(Id, isize)| *count > param_threshold)
+                .map(|(forum_id, _count)| forum_id)
+                ;
+
+            let result = count1
+                .join(&count2)
+                .semijoin(&large_forums)
+                .map(|(forum_id, (count1, count2)): (Id, (isize, isize))| (
+                    (std::isize::MAX - (count2 - count1).abs(), forum_id), // sort: -|count2 - count1|, +forum_id
+                    vec![forum_id.to_string(), count1.to_string(), count2.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_classes_input, tag_hastype_tagclass_input,
+                forum_hasmember_input, forum_containerof_post_input, has_tag_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag_class(path.as_str(), index, peers), &mut tag_classes_input, next_time);
+        input_insert_vec(
+            load_connection("static/tag_hasType_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_hastype_tagclass_input,
+            next_time
+        );
+        input_insert_vec(load_forum_has_member(path.as_str(), index, peers), &mut forum_hasmember_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_containerOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut forum_containerof_post_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_classes_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "forum-hasmember-person" => {
+                    if create {
+                        forum_hasmember_input.insert(d);
+                    } else {
+                        forum_hasmember_input.remove(d);
+                    }
+                },
+                "forum-containerof-post" => {
+                    if create {
+                        forum_containerof_post_input.insert(d);
+                    } else {
+                        forum_containerof_post_input.remove(d);
+                    }
+                },
+                "post-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_classes_input.advance_to(next_time);
+        tag_classes_input.flush();
+        tag_hastype_tagclass_input.advance_to(next_time);
+        tag_hastype_tagclass_input.flush();
+        forum_hasmember_input.advance_to(next_time);
+        forum_hasmember_input.flush();
+        forum_containerof_post_input.advance_to(next_time);
+        forum_containerof_post_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,228 @@
+/*
+LDBC SNB BI query 11. Friend triangles
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-11.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Threshold};
+use differential_dataflow::operators::arrange::ArrangeByKey;
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, country_cities};
+use crate::lib::delta_query::half_join;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+use std::cmp::{min, max};
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+    let param_start_ = params[1].clone();
+    let param_end_ = params[2].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+        let param_start = parse_datetime(param_start_.clone());
+        let param_end = parse_datetime(param_end_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country.clone()))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // knows edges created in the given time frame between people of the country,
+            // oriented from the lower id to the higher one: (a, b) where a < b
+            let edges = knows
+                .filter(move |conn| param_start <= *conn.created() && *conn.created() <= param_end)
+                .map(|conn| (min(conn.a().clone(), conn.b().clone()), max(conn.a().clone(), conn.b().clone())))
+                .semijoin(&people_in_country)
+                .map(|(a, b)| (b, a))
+                .semijoin(&people_in_country)
+                .map(|(b, a)| (a, b))
+                .distinct()
+                ;
+
+            // the only state kept for the triangles are these three arrangements of the edges
+            let forward = edges.arrange_by_key(); // a -> b
+            let reverse = edges.map(|(a, b)| (b, a)).arrange_by_key(); // b -> a
+            let pairs = edges.map(|edge| (edge, ())).arrange_by_key(); // (a, b) -> ()
+
+            // A triangle a < b < c consists of the edges (a, b), (b, c) and (a, c).
+            // Each of the three delta terms below takes the changes of the edges in one of these roles,
+            // and extends them to triangles using the other two roles. Roles before the changed one see
+            // the edges including the current changes, roles after it only see the edges before them.
+
+            // changed edge is (a, b)
+            let delta_ab = half_join(
+                &half_join(&edges.map(|(a, b)| (b, a)), &forward, true, |&b, &a, &c| ((a, c), b)),
+                &pairs, true, |&(a, c), &b, &()| (a, b, c)
+            );
+
+            // changed edge is (b, c)
+            let delta_bc = half_join(
+                &half_join(&edges, &reverse, false, |&b, &c, &a| ((a, c), b)),
+                &pairs, true, |&(a, c), &b, &()| (a, b, c)
+            );
+
+            // changed edge is (a, c)
+            let delta_ac = half_join(
+                &half_join(&edges, &forward, false, |&a, &c, &b| ((b, c), a))
+                    .filter(|((b, c), _a)| b < c),
+                &pairs, false, |&(b, c), &a, &()| (a, b, c)
+            );
+
+            let triangles = delta_ab
+                .concat(&delta_bc)
+                .concat(&delta_ac)
+                ; // -> (a, b, c)
+
+            // add the country itself once, to have a result even without triangles (corrected later)
+            let result = triangles
+                .map(|_triangle| ())
+                .concat(
+                    &place
+                        .filter(move |x| param_country.eq(x.name()))
+                        .map(|_country| ())
+                )
+                .count()
+                .map(|((), count)| (
+                    (),
+                    vec![(count - 1).to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 1)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                knows_input, located_in_input, place_input, place_is_part_of_place_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
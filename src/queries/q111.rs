@@ -0,0 +1,343 @@
+/*
+Legacy LDBC SNB BI query 11. Unrelated replies
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, country_cities, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+    let param_blacklist_: Vec<String> = params[1].split(';')
+        .filter(|w| !w.is_empty())
+        .map(|x| x.to_string())
+        .collect();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+        let param_blacklist = param_blacklist_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut comment_input,
+            mut tag_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut has_tag_input,
+            mut has_creator_input,
+            mut reply_of_input,
+            mut likes_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+            // likes for comments AND posts
+            let (likes_input, likes) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // replies without any of the blacklisted words in their content
+            let replies = reply_of
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> reply_id, parent_id
+                .semijoin(
+                    &comment
+                        .filter(move |comment| !param_blacklist.iter().any(|word| comment.content().contains(word.as_str())))
+                        .map(|comment| comment.id().clone())
+                )
+                ;
+
+            let message_tags = has_tag
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> message_id, tag_id
+                ;
+
+            // replies sharing at least one Tag with their parent
+            let related_replies = replies
+                .join_map(
+                    &message_tags,
+                    |reply_id, parent_id, tag_id| ((parent_id.clone(), tag_id.clone()), reply_id.clone())
+                )
+                .semijoin(&message_tags)
+                .map(|((_parent_id, _tag_id), reply_id)| reply_id)
+                .distinct()
+                ;
+
+            // unrelated replies with their creators living in the given country
+            let unrelated_replies = replies
+                .antijoin(&related_replies)
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |reply_id, _parent_id, person_id| (person_id.clone(), reply_id.clone())
+                )
+                .semijoin(&people_in_country)
+                .map(|(person_id, reply_id)| (reply_id, person_id))
+                ;
+
+            // replies without likes have a like count of 0
+            let like_counts = left_outer_join(
+                &unrelated_replies,
+                &likes.map(|conn| conn.b().clone()).count()
+            )
+                .map(|(reply_id, (person_id, count))| (reply_id, (person_id, count.unwrap_or(0))))
+                ;
+
+            // count the replies and sum their likes for each person and Tag of the replies
+            let counts = like_counts
+                .join_map(
+                    &message_tags,
+                    |_reply_id, &(person_id, like_count), tag_id| ((person_id, tag_id.clone()), like_count)
+                )
+                .reduce(|_key, input, output| {
+                    let mut like_sum: isize = 0;
+                    let mut reply_count: isize = 0;
+                    for (like_count, diff) in input.iter() {
+                        like_sum += **like_count * diff;
+                        reply_count += diff;
+                    }
+                    output.push(((like_sum, reply_count), 1));
+                })
+                ; // -> ((person_id, tag_id), (like_count, reply_count))
+
+            let result = counts
+                .map(|((person_id, tag_id), counts)| (tag_id, (person_id, counts)))
+                .join_map(
+                    &tag.map(|tag| (tag.id().clone(), tag.name().clone())),
+                    |_tag_id, &(person_id, (like_count, reply_count)): &(Id, (isize, isize)), name| (
+                        (std::isize::MAX - like_count, person_id, name.clone()), // sort: -like_count, +person_id, +name
+                        vec![person_id.to_string(), name.to_string(), like_count.to_string(), reply_count.to_string()]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                comment_input, tag_input, located_in_input, place_input, place_is_part_of_place_input,
+                has_tag_input, has_creator_input, reply_of_input, likes_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+        // insert likes relations
+        input_insert_vec(
+            load_person_likes_post(path.as_str(), index, peers),
+            &mut likes_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_person_likes_comment(path.as_str(), index, peers),
+            &mut likes_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(comment_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "message-hastag-tag" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                "person-likes-message" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        likes_input.insert(d);
+                    } else {
+                        likes_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+        likes_input.advance_to(next_time);
+        likes_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,210 @@
+/*
+Legacy LDBC SNB BI query 15. Social normals
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, country_cities, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Reduce};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // number of friends in the country for each person of the country, 0 if there is none
+            let friend_counts = left_outer_join(
+                &people_in_country.map(|person_id| (person_id, ())),
+                &bi_knows
+                    .semijoin(&people_in_country)
+                    .map(|(person_id, friend_id)| (friend_id, person_id))
+                    .semijoin(&people_in_country)
+                    .map(|(_friend_id, person_id)| person_id)
+                    .count()
+            )
+                .map(|(person_id, ((), count))| (person_id, count.unwrap_or(0)))
+                ; // -> (person_id, friend_count)
+
+            // the social normal is the floor of the average friend count, aggregated over
+            // all persons of the country with the same (empty) key
+            let social_normal = friend_counts
+                .map(|(_person_id, count)| ((), count))
+                .reduce(|_key, input, output| {
+                    let mut sum: isize = 0;
+                    let mut persons: isize = 0;
+                    for (count, diff) in input.iter() {
+                        sum += **count * diff;
+                        persons += diff;
+                    }
+                    output.push((sum / persons, 1));
+                })
+                .map(|((), average)| average)
+                ;
+
+            // persons whose friend count equals the social normal
+            let result = friend_counts
+                .map(|(person_id, count)| (count, person_id))
+                .semijoin(&social_normal)
+                .map(|(count, person_id)| (
+                    person_id, // sort: +person_id
+                    vec![person_id.to_string(), count.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                knows_input, located_in_input, place_input, place_is_part_of_place_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,251 @@
+/*
+LDBC SNB BI query 12. How many persons have a given number of messages
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-12.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Iterate, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, left_outer_join};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_date_ = params[0].clone();
+    let param_length_threshold = params[1].parse::<usize>().unwrap();
+    let param_languages_: Vec<String> = params[2].split(';').map(|x| x.to_string()).collect();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_date = parse_datetime(param_date_.clone());
+        let param_languages = param_languages_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut post_input,
+            mut comment_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (post_input, post) = scope.new_collection::<Post, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // posts written in one of the given languages
+            let posts_in_language = post
+                .filter(move |post| param_languages.contains(post.lang()))
+                .map(|post| post.id().clone())
+                ;
+
+            // messages in the given languages, where the language of a comment
+            // is the language of the post at the root of its thread
+            let messages_in_language = posts_in_language
+                .iterate(|transitive| {
+                    let posts = posts_in_language.enter(&transitive.scope());
+                    let replies = reply_of
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> parent_id, reply_id
+                        .enter(&transitive.scope());
+
+                    transitive
+                        .map(|message_id| (message_id, ()))
+                        .join_map(&replies, |_parent_id, _dummy, reply_id| reply_id.clone())
+                        .concat(&posts)
+                        .distinct()
+                })
+                ;
+
+            // messages created after the given date, which have content shorter than the threshold
+            let messages = post
+                .map(|post| (post.id().clone(), (post.created().clone(), post.length().clone())))
+                .concat(
+                    &comment.map(|comment| (comment.id().clone(), (comment.created().clone(), comment.length().clone())))
+                )
+                .filter(move |(_message_id, (created, length))|
+                    *created > param_date && 0 < *length && *length < param_length_threshold
+                )
+                .map(|(message_id, _data)| message_id)
+                ;
+
+            // count the matching messages of each person
+            let message_counts = messages
+                .map(|message_id| (message_id, ()))
+                .semijoin(&messages_in_language)
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |_message_id, _dummy, person_id| person_id.clone()
+                )
+                .count()
+                ; // -> (person_id, message_count)
+
+            // persons without messages have a message count of 0
+            let result = left_outer_join(
+                &person.map(|person| (person.id().clone(), ())),
+                &message_counts
+            )
+                .map(|(_person_id, ((), count))| count.unwrap_or(0))
+                .count()
+                .map(|(message_count, person_count)| (
+                    (std::isize::MAX - person_count, std::isize::MAX - message_count), // sort: -person_count, -message_count
+                    vec![message_count.to_string(), person_count.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 1000)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, post_input, comment_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(load_post(path.as_str(), index, peers), &mut post_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "post" => {
+                    let post = parse_post(change_row);
+                    if create {
+                        post_input.insert(post);
+                    } else {
+                        post_input.remove(post);
+                    }
+                },
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "message-hascreator-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        post_input.advance_to(next_time);
+        post_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
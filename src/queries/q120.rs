@@ -0,0 +1,192 @@
+/*
+Legacy LDBC SNB BI query 20. High-level topics
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, tag_class_descendants};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_classes_: Vec<String> = params[0].split(';').map(|x| x.to_string()).collect();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag_classes = param_tag_classes_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_classes_input,
+            mut tag_class_is_subclass_of_input,
+            mut tag_hastype_tagclass_input,
+            mut has_tag_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_classes_input, tag_classes) = scope.new_collection::<TagClass, _>();
+            let (tag_class_is_subclass_of_input, tag_class_is_subclass_of) = scope.new_collection::<Connection, _>();
+            let (tag_hastype_tagclass_input, tag_hastype_tagclass) = scope.new_collection::<Connection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+
+            // the given TagClasses with their names
+            let selected_tag_classes = tag_classes
+                .filter(move |x| param_tag_classes.contains(x.name()))
+                .map(|x| (x.id().clone(), x.name().clone()))
+                ;
+
+            // Tags of the given TagClasses, directly or through any of their subclasses
+            let tag_class_tags = tag_class_descendants(&tag_classes, &tag_class_is_subclass_of)
+                .semijoin(&selected_tag_classes.map(|(tag_class_id, _name)| tag_class_id))
+                .map(|(tag_class_id, descendant_id)| (descendant_id, tag_class_id))
+                .join_map(
+                    &tag_hastype_tagclass.map(|conn| (conn.b().clone(), conn.a().clone())), // -> tag_class_id, tag_id
+                    |_descendant_id, tag_class_id, tag_id| (tag_id.clone(), tag_class_id.clone())
+                )
+                ; // -> (tag_id, tag_class_id)
+
+            // count the distinct messages with a Tag of each TagClass
+            let counts = has_tag
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, message_id
+                .join_map(&tag_class_tags, |_tag_id, message_id, tag_class_id| (tag_class_id.clone(), message_id.clone()))
+                .distinct()
+                .map(|(tag_class_id, _message_id)| tag_class_id)
+                .count()
+                ; // -> (tag_class_id, message_count)
+
+            let result = counts
+                .join_map(
+                    &selected_tag_classes,
+                    |_tag_class_id, &count, name| (
+                        (std::isize::MAX - count, name.clone()), // sort: -count, +name
+                        vec![name.to_string(), count.to_string()]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_classes_input, tag_class_is_subclass_of_input, tag_hastype_tagclass_input, has_tag_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag_class(path.as_str(), index, peers), &mut tag_classes_input, next_time);
+        input_insert_vec(
+            load_connection("static/tagclass_isSubclassOf_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_class_is_subclass_of_input,
+            next_time
+        );
+        input_insert_vec(
+            load_connection("static/tag_hasType_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_hastype_tagclass_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_classes_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "message-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_classes_input.advance_to(next_time);
+        tag_classes_input.flush();
+        tag_class_is_subclass_of_input.advance_to(next_time);
+        tag_class_is_subclass_of_input.flush();
+        tag_hastype_tagclass_input.advance_to(next_time);
+        tag_hastype_tagclass_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
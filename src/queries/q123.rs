@@ -0,0 +1,227 @@
+/*
+Legacy LDBC SNB BI query 23. Holiday destinations
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, country_cities, containing_places};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+use chrono::Datelike;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut has_creator_input,
+            mut message_located_in_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // locations for comments AND posts
+            let (message_located_in_input, message_located_in) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country.clone()))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // messages of these persons, with the country they were sent from and the month of their creation.
+            // isLocatedIn relations are created at the same time as the message itself.
+            let messages = has_creator
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, message_id
+                .semijoin(&people_in_country)
+                .map(|(_person_id, message_id)| (message_id, ()))
+                .join_map(
+                    &message_located_in.map(|conn| (conn.a().clone(), (conn.b().clone(), conn.created().clone()))),
+                    |_message_id, _dummy, &(place_id, created)|
+                        (place_id, chrono::NaiveDateTime::from_timestamp(created, 0).month())
+                )
+                .join_map(
+                    &containing_places(&place, &place_is_part_of_place, "country".to_string()),
+                    |_place_id, month, country_id| (country_id.clone(), month.clone())
+                )
+                ; // -> (country_id, month)
+
+            // count the messages sent from other countries in each (destination, month) group
+            let result = messages
+                .join_map(
+                    &place.map(|place| (place.id().clone(), place.name().clone())),
+                    |_country_id, month, name| (name.clone(), month.clone())
+                )
+                .filter(move |(name, _month)| !param_country.eq(name))
+                .count()
+                .map(|((name, month), count): ((String, u32), isize)| (
+                    (std::isize::MAX - count, name.clone(), month), // sort: -count, +name, +month
+                    vec![count.to_string(), name.to_string(), month.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                located_in_input, place_input, place_is_part_of_place_input,
+                has_creator_input, message_located_in_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert isLocatedIn relations of messages
+        input_insert_vec(
+            load_post_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_comment_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(located_in_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "message-islocatedin-place" => {
+                    if create {
+                        message_located_in_input.insert(d);
+                    } else {
+                        message_located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        message_located_in_input.advance_to(next_time);
+        message_located_in_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
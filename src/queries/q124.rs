@@ -0,0 +1,267 @@
+/*
+Legacy LDBC SNB BI query 24. Messages by topic and continent
+https://arxiv.org/pdf/2001.02299.pdf
+
+Note: in the newer version of LDBC SNB BI this query is omitted.
+*/
+
+use timely::dataflow::ProbeHandle;
+use differential_dataflow::input::Input;
+
+use crate::lib::helpers::{print_trace, input_insert_vec, limit, tag_class_tags, containing_places, left_outer_join};
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use differential_dataflow::operators::{Count, Join, Reduce, Threshold};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+use chrono::Datelike;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_class_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag_class = param_tag_class_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_classes_input,
+            mut tag_hastype_tagclass_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut has_tag_input,
+            mut message_located_in_input,
+            mut likes_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_classes_input, tag_classes) = scope.new_collection::<TagClass, _>();
+            let (tag_hastype_tagclass_input, tag_hastype_tagclass) = scope.new_collection::<Connection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // locations for comments AND posts
+            let (message_located_in_input, message_located_in) = scope.new_collection::<DynamicConnection, _>();
+            // likes for comments AND posts
+            let (likes_input, likes) = scope.new_collection::<DynamicConnection, _>();
+
+            // messages which have at least one Tag of the given TagClass
+            let class_messages = has_tag
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, message_id
+                .semijoin(&tag_class_tags(&tag_classes, &tag_hastype_tagclass, param_tag_class))
+                .map(|(_tag_id, message_id)| message_id)
+                .distinct()
+                ;
+
+            // the continent and the month of these messages, with their number of likes.
+            // isLocatedIn relations are created at the same time as the message itself.
+            let messages = left_outer_join(
+                &message_located_in
+                    .map(|conn| (conn.a().clone(), (conn.b().clone(), conn.created().clone()))) // -> message_id, (place_id, created)
+                    .semijoin(&class_messages),
+                &likes.map(|conn| conn.b().clone()).count()
+            )
+                .map(|(_message_id, ((place_id, created), count))| {
+                    let date = chrono::NaiveDateTime::from_timestamp(created, 0);
+                    (place_id, (date.year(), date.month(), count.unwrap_or(0)))
+                })
+                .join_map(
+                    &containing_places(&place, &place_is_part_of_place, "continent".to_string()),
+                    |_place_id, &(year, month, like_count), continent_id| ((year, month, continent_id.clone()), like_count)
+                )
+                ; // -> ((year, month, continent_id), like_count)
+
+            // count the messages and sum their likes in each (year, month, continent) group
+            let groups = messages
+                .reduce(|_group, input, output| {
+                    let mut message_count: isize = 0;
+                    let mut like_count: isize = 0;
+                    for (likes, diff) in input.iter() {
+                        message_count += diff;
+                        like_count += **likes * diff;
+                    }
+                    output.push(((message_count, like_count), 1));
+                })
+                ; // -> ((year, month, continent_id), (message_count, like_count))
+
+            let result = groups
+                .map(|((year, month, continent_id), counts)| (continent_id, (year, month, counts)))
+                .join_map(
+                    &place.map(|place| (place.id().clone(), place.name().clone())),
+                    |_continent_id, &(year, month, (message_count, like_count)), name| {
+                        // inverted bytes, followed by the maximal byte, order the names descending
+                        let name_desc: Vec<u8> = name.bytes().map(|b| std::u8::MAX - b).chain(Some(std::u8::MAX)).collect();
+                        (
+                            (year, month, name_desc), // sort: +year, +month, -name
+                            vec![
+                                message_count.to_string(), like_count.to_string(),
+                                year.to_string(), month.to_string(), name.to_string()
+                            ]
+                        )
+                    }
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_classes_input, tag_hastype_tagclass_input, place_input, place_is_part_of_place_input,
+                has_tag_input, message_located_in_input, likes_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag_class(path.as_str(), index, peers), &mut tag_classes_input, next_time);
+        input_insert_vec(
+            load_connection("static/tag_hasType_tagclass_0_0.csv", path.as_str(), index, peers),
+            &mut tag_hastype_tagclass_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert isLocatedIn relations of messages
+        input_insert_vec(
+            load_post_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_comment_is_located_in(path.as_str(), index, peers),
+            &mut message_located_in_input,
+            next_time
+        );
+        // insert likes relations
+        input_insert_vec(
+            load_person_likes_post(path.as_str(), index, peers),
+            &mut likes_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_person_likes_comment(path.as_str(), index, peers),
+            &mut likes_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_classes_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "message-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                "message-islocatedin-place" => {
+                    if create {
+                        message_located_in_input.insert(d);
+                    } else {
+                        message_located_in_input.remove(d);
+                    }
+                },
+                "person-likes-message" => {
+                    if create {
+                        likes_input.insert(d);
+                    } else {
+                        likes_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_classes_input.advance_to(next_time);
+        tag_classes_input.flush();
+        tag_hastype_tagclass_input.advance_to(next_time);
+        tag_hastype_tagclass_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        message_located_in_input.advance_to(next_time);
+        message_located_in_input.flush();
+        likes_input.advance_to(next_time);
+        likes_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,315 @@
+/*
+LDBC SNB BI query 13. Zombies in a country
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-13.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Reduce};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, country_cities, left_outer_join, months_between};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+    let param_end_date_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+        let param_end_date = parse_datetime(param_end_date_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut post_input,
+            mut comment_input,
+            mut has_creator_input,
+            mut likes_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+            let (post_input, post) = scope.new_collection::<Post, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // likes for comments AND posts
+            let (likes_input, likes) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons created before the end date
+            let persons = person
+                .filter(move |person| *person.created() < param_end_date)
+                .map(|person| (person.id().clone(), person.created().clone()))
+                ; // -> (person_id, created)
+
+            // persons living in the given country
+            let people_in_country = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country))
+                .map(|(_place_id, person_id)| person_id)
+                ;
+
+            // number of messages of each person created before the end date
+            let message_counts = post
+                .map(|post| (post.id().clone(), post.created().clone()))
+                .concat(
+                    &comment.map(|comment| (comment.id().clone(), comment.created().clone()))
+                )
+                .filter(move |(_message_id, created)| *created < param_end_date)
+                .map(|(message_id, _created)| (message_id, ()))
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |_message_id, _dummy, person_id| person_id.clone()
+                )
+                .count()
+                ; // -> (person_id, message_count)
+
+            // zombies created less than one message per month on average since they joined
+            let zombies = left_outer_join(&persons.semijoin(&people_in_country), &message_counts)
+                .filter(move |(_person_id, (created, count))|
+                    (count.unwrap_or(0) as i64) < months_between(*created, param_end_date)
+                )
+                .map(|(person_id, _data)| person_id)
+                ;
+
+            // likes received by the zombies, only from persons created before the end date
+            let zombie_likes = likes
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> liker_person_id, message_id
+                .semijoin(&persons.map(|(person_id, _created)| person_id))
+                .map(|(liker, message_id)| (message_id, liker))
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |_message_id, liker, creator| (creator.clone(), liker.clone())
+                )
+                .semijoin(&zombies)
+                ; // -> (zombie_id, liker_person_id)
+
+            // count the likes from zombies and all the likes of each zombie
+            let like_counts = zombie_likes
+                .map(|(zombie, liker)| (liker, zombie))
+                .semijoin(&zombies)
+                .map(|(_liker, zombie)| (zombie, (1, 0)))
+                .concat(
+                    &zombie_likes.map(|(zombie, _liker)| (zombie, (0, 1)))
+                )
+                .concat(
+                    &zombies.map(|zombie| (zombie, (0, 0)))
+                ) // make sure to have each zombie, even without likes
+                .reduce(|_zombie, input, output| {
+                    let mut zombie_like_count: isize = 0;
+                    let mut total_like_count: isize = 0;
+                    for ((zombie_like, like), diff) in input.iter() {
+                        zombie_like_count += zombie_like * diff;
+                        total_like_count += like * diff;
+                    }
+                    output.push(((zombie_like_count, total_like_count), 1));
+                })
+                ; // -> (zombie_id, (zombie_like_count, total_like_count))
+
+            let result = like_counts
+                .map(|(zombie, (zombie_like_count, total_like_count))| {
+                    let score = if total_like_count == 0 {
+                        0.0
+                    } else {
+                        zombie_like_count as f64 / total_like_count as f64
+                    };
+                    (
+                        // floats are not Ord, so the score is sorted as a scaled integer
+                        (std::isize::MAX - (score * 10000000000.0) as isize, zombie), // sort: -score, +zombie_id
+                        vec![
+                            zombie.to_string(), zombie_like_count.to_string(),
+                            total_like_count.to_string(), score.to_string()
+                        ]
+                    )
+                })
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, located_in_input, place_input, place_is_part_of_place_input,
+                post_input, comment_input, has_creator_input, likes_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        input_insert_vec(load_post(path.as_str(), index, peers), &mut post_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert likes relations
+        input_insert_vec(
+            load_person_likes_post(path.as_str(), index, peers),
+            &mut likes_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_person_likes_comment(path.as_str(), index, peers),
+            &mut likes_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "post" => {
+                    let post = parse_post(change_row);
+                    if create {
+                        post_input.insert(post);
+                    } else {
+                        post_input.remove(post);
+                    }
+                },
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "message-hascreator-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "person-likes-message" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        likes_input.insert(d);
+                    } else {
+                        likes_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        post_input.advance_to(next_time);
+        post_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        likes_input.advance_to(next_time);
+        likes_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,309 @@
+/*
+LDBC SNB BI query 14. International dialog
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-14.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Reduce, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, limit_per_key, print_trace, country_cities};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country1_ = params[0].clone();
+    let param_country2_ = params[1].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country1 = param_country1_.clone();
+        let param_country2 = param_country2_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut knows_input,
+            mut located_in_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut has_creator_input,
+            mut reply_of_input,
+            mut likes_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+            // likes for comments AND posts
+            let (likes_input, likes) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons of the first country, with the city they live in
+            let people_in_country1 = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country1))
+                .map(|(city_id, person_id)| (person_id, city_id))
+                ;
+
+            // persons of the second country
+            let people_in_country2 = located_in
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> place_id, person_id
+                .semijoin(&country_cities(&place, &place_is_part_of_place, param_country2))
+                .map(|(_city_id, person_id)| person_id)
+                ;
+
+            let creators = has_creator
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> message_id, person_id
+                ;
+
+            // pairs of persons where the first one replied to a message of the second one
+            let replies = reply_of
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> comment_id, parent_id
+                .join_map(&creators, |_comment_id, parent_id, replier| (parent_id.clone(), replier.clone()))
+                .join_map(&creators, |_parent_id, replier, parent_creator| (replier.clone(), parent_creator.clone()))
+                .distinct()
+                ; // -> (replier_id, creator_id)
+
+            // pairs of persons where the first one liked a message of the second one
+            let likes = likes
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> message_id, liker_person_id
+                .join_map(&creators, |_message_id, liker, creator| (liker.clone(), creator.clone()))
+                .distinct()
+                ; // -> (liker_id, creator_id)
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // each kind of interaction adds its score once, only pairs with any interaction are considered
+            let scores = replies.map(|(p1, p2)| ((p1, p2), 4))
+                .concat(&replies.map(|(p2, p1)| ((p1, p2), 1)))
+                .concat(&bi_knows.map(|(p1, p2)| ((p1, p2), 15)))
+                .concat(&likes.map(|(p1, p2)| ((p1, p2), 10)))
+                .concat(&likes.map(|(p2, p1)| ((p1, p2), 1)))
+                .map(|((p1, p2), score)| (p2, (p1, score)))
+                .semijoin(&people_in_country2)
+                .map(|(p2, (p1, score))| ((p1, p2), score))
+                .reduce(|_pair, input, output| {
+                    let mut sum: isize = 0;
+                    for (score, diff) in input.iter() {
+                        sum += **score * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                ; // -> ((person1_id, person2_id), score)
+
+            // the best scoring pair in each city of the first country
+            let top_pairs = limit_per_key(
+                &scores
+                    .map(|((p1, p2), score)| (p1, (p2, score)))
+                    .join_map(
+                        &people_in_country1,
+                        |p1, &(p2, score), city_id| (city_id.clone(), (std::isize::MAX - score, p1.clone(), p2))
+                    ),
+                1
+            ); // -> (city_id, (-score, person1_id, person2_id))
+
+            let result = top_pairs
+                .join_map(
+                    &place.map(|place| (place.id().clone(), place.name().clone())),
+                    |_city_id, &(neg_score, p1, p2), city_name| (
+                        (neg_score, p1, p2), // sort: -score, +person1_id, +person2_id
+                        vec![
+                            p1.to_string(), p2.to_string(), city_name.to_string(),
+                            (std::isize::MAX - neg_score).to_string()
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                knows_input, located_in_input, place_input, place_is_part_of_place_input,
+                has_creator_input, reply_of_input, likes_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+        // insert likes relations
+        input_insert_vec(
+            load_person_likes_post(path.as_str(), index, peers),
+            &mut likes_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_person_likes_comment(path.as_str(), index, peers),
+            &mut likes_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "person-islocatedin-place" => {
+                    if create {
+                        located_in_input.insert(d);
+                    } else {
+                        located_in_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                "person-likes-message" => {
+                    if create {
+                        likes_input.insert(d);
+                    } else {
+                        likes_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+        likes_input.advance_to(next_time);
+        likes_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
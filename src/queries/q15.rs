@@ -1,6 +1,6 @@
 /*
-Legacy LDBC SNB BI query 15. Trusted connection paths through forums created in a given timeframe
-https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-15.pdf
+Legacy LDBC SNB BI query 25. Trusted connection paths through forums created in a given timeframe
+https://arxiv.org/pdf/2001.02299.pdf
 */
 
 use timely::dataflow::{ProbeHandle, Scope};
@@ -8,17 +8,15 @@ use differential_dataflow::input::Input;
 use differential_dataflow::operators::{Reduce, Join, Iterate, Consolidate, Threshold, Count};
 use differential_dataflow::Collection;
 use differential_dataflow::lattice::Lattice;
-use timely::order::Product;
-use differential_dataflow::operators::iterate::Variable;
 
-use crate::lib::helpers::input_insert_vec;
-use crate::lib::loader::{load_dynamic_connection, load_forum, parse_datetime};
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, shortest_paths, enumerate_shortest_paths};
+use crate::lib::loader::{load_data, load_dynamic_connection, load_forum, parse_datetime};
 use crate::lib::types::*;
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
 use std::time::Instant;
 
-pub fn run(path: String, _change_path: String, params: &Vec<String>) {
-    // FIXME: WIP not properly implemented.
-
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
     // Based on the work of Frank McSherry:
     // https://github.com/frankmcsherry/blog/blob/81e9555bbee110954f2c3d35caf86ea7e7612fa6/posts/2019-06-13.md
 
@@ -43,10 +41,10 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
 
         // create dataflow
         let (
+            mut trace,
             mut query_input,
             mut knows_input,
-            mut post_hascreator_input,
-            mut comment_hascreator_input,
+            mut has_creator_input,
             mut forum_containerof_post_input,
             mut reply_of_input,
             mut forum_input
@@ -54,18 +52,17 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
         worker.dataflow::<usize, _, _>(|scope| {
             let (query_input, query) = scope.new_collection::<((Id, Id),(Date, Date)), _>(); // FIXME shouldn't be a collection?
             let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
-            let (post_hascreator_input, post_hascreator) = scope.new_collection::<DynamicConnection, _>();
+            // hasCreator for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
             let (forum_containerof_post_input, forum_containerof_post) = scope.new_collection::<DynamicConnection, _>();
 
             // replyOf for comments AND posts
             let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
 
-            let (comment_hascreator_input, comment_hascreator) = scope.new_collection::<DynamicConnection, _>();
-
             let (forum_input, forum) = scope.new_collection::<Forum, _>();
 
             // posts should be: (post_id, (creator_id, forum_id)
-            let posts = post_hascreator
+            let posts = has_creator
                 .map(|conn| (conn.a().clone(), conn.b().clone())) // -> post_id, person_id
                 .join(
                     &forum_containerof_post.map(|conn| (conn.b().clone(), conn.a().clone())) // -> post_id, forum_id
@@ -74,7 +71,7 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
 
             // comments should be: (comment_id, (creator_id, parent_id),
             // where parent_id can be a post id or comment id
-            let comms = comment_hascreator
+            let comms = has_creator
                 .map(|conn| (conn.a().clone(), conn.b().clone())) // -> comment_id, person_id
                 .join(
                     &reply_of.map(|conn| (conn.a().clone(), conn.b().clone())) // -> comment_id, parent_id
@@ -82,9 +79,17 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
                 ; // -> comment_id, (creator_id, parent_id)
 
 
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
             // 1. Determine edges in shortest paths, for each query.
             let goals = query.map(|(goal,_bounds)| goal).distinct();
-            let shortest_edges = shortest_paths(&knows.map(|conn| (conn.a().clone(), conn.b().clone())), &goals);
+            let shortest_edges = shortest_paths(&bi_knows, &goals);
 
             // 2. Score each edge, broken down by the root post.
             let oriented_edges = shortest_edges.map(|(_,(x,y))| {
@@ -98,7 +103,7 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
             let edge_scores = edge_scores.map(|((x,y),f)| ((y,x),f)).concat(&edge_scores);
 
             // 3. Merge queries and scores, filter by start and end dates.
-            let _scored_edges =
+            let scored_edges =
             query
                 .join_map(&shortest_edges, |&goal, &bounds, &edge| (edge, (goal, bounds)))
                 .join_map(&edge_scores, |&edge, &(goal, bounds), &forum| (forum, (goal, edge, bounds)))
@@ -112,40 +117,69 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
                 .concat(&shortest_edges)
                 .count()
                 .map(|(x,c)| (x,c-1))
-                .inspect(|x| println!("SCORED: {:?}", x))
-                .probe_with(&mut probe)
-                ;
+                ; // -> ((goal, edge), score), where the score is counted in half points
+
+            // 4. Reconstruct paths and scores.
+            let paths = enumerate_shortest_paths(&goals, &shortest_edges); // -> (goal, path)
+
+            // the score of a path is the sum of the scores of its edges
+            let scored_paths = paths
+                .flat_map(|(goal, path)| {
+                    let edges: Vec<_> = path.windows(2).map(|edge| (goal, (edge[0], edge[1]))).collect();
+                    edges.into_iter().map(move |edge| (edge, path.clone()))
+                })
+                .join_map(&scored_edges, |_goal_edge, path, &score| (path.clone(), score))
+                // a zero-length path, from a person to itself, has no edges to be scored
+                .concat(&paths.filter(|(_goal, path)| path.len() == 1).map(|(_goal, path)| (path, 0)))
+                .reduce(|_path, input, output| {
+                    let mut sum: isize = 0;
+                    for (score, diff) in input.iter() {
+                        sum += **score * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                ; // -> (path, score)
 
-            // let filtered_edges: Collection<_, ((Node, Node), (Edge, usize))>
-            //     = unimplemented!();
+            // 5. Announce massive success!
+            let result = scored_paths
+                .map(|(path, score)| (
+                    (std::isize::MAX - score, path.clone()), // sort: -score, +path
+                    vec![
+                        path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(";"),
+                        (score as f64 / 2.0).to_string()
+                    ]
+                ))
+                ;
 
-            // // 4. Reconstruct paths and scores.
-            // let scored_paths: Collection<_, ((Node, Node), (Vec<Node>, usize))>
-            //     = unimplemented!();
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
 
-            // 5. Announce massive success!
-            // shortest_edges.inspect(|x| println!("WOW:\t{:?}", x));
+            arrangement.stream.probe_with(&mut probe);
 
             return (
-                query_input, knows_input, post_hascreator_input, comment_hascreator_input,
+                arrangement.trace,
+                query_input, knows_input, has_creator_input,
                 forum_containerof_post_input, reply_of_input,
                 forum_input
             );
         });
 
-        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
-        timer = Instant::now();
-
         // add inputs
-        let next_time: usize  = 1;
+        let mut next_time: usize = 1;
         input_insert_vec(
             load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
             &mut knows_input,
             next_time,
         );
+        // insert hasCreator relations both for posts and comments, to handle them together in the dataflow
         input_insert_vec(
             load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
-            &mut post_hascreator_input,
+            &mut has_creator_input,
+            0, // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
             next_time,
         );
         input_insert_vec(
@@ -166,176 +200,104 @@ pub fn run(path: String, _change_path: String, params: &Vec<String>) {
             next_time,
         );
 
+        input_insert_vec(load_forum(path.as_str(), index, peers), &mut forum_input, next_time);
+
         input_insert_vec(
-            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
-            &mut comment_hascreator_input,
+            vec![((param_person_a, param_person_b), (param_from, param_to))],
+            &mut query_input,
             next_time,
         );
 
-        input_insert_vec(load_forum(path.as_str(), index, peers), &mut forum_input, next_time);
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
 
-        query_input.insert(( // FIXME
-            (param_person_a, param_person_b),
-            (param_from, param_to)
-        ));
-        query_input.advance_to(next_time);
-        query_input.flush();
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
 
         eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
-        // timer = Instant::now();
 
-        while probe.less_than(&1) {
-            worker.step();
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
         }
 
-    }).expect("Timely computation failed");
-}
+        println!(" ---------------------------------------------------------------------- ");
 
-/// Describes shortest paths between query node pairs.
-///
-/// The `bijkstra` method takes as input a collection of edges (described as pairs
-/// of source and target node) and a collection of query node pairs (again, source
-/// and target nodes). Its intent is to describe the set of shortest paths between
-/// each pair of query nodes.
-///
-/// The method's output describes a set of directed edges for each input query pair.
-/// The set of directed edges are the directed acyclic graph of edges on shortest
-/// paths from the source of the query pair to the target of the query pair.
-/// There may be multiple paths, and the paths may fork apart and merge together.
-fn shortest_paths<G>(
-    edges: &Collection<G, (Id, Id)>,
-    goals: &Collection<G, (Id, Id)>,
-) -> Collection<G, ((Id, Id), (Id, Id))>
-where
-    G: Scope,
-    G::Timestamp: Lattice + Ord,
-{
-    // Iteratively develop reachability information.
-    edges.scope().iterative::<u64, _, _>(|inner| {
-
-        // Our plan is to start evolving distances from both sources and destinations.
-        // The evolution from a source or destination should continue as long as there
-        // is a corresponding destination or source that has not yet been reached.
-
-        let goals = goals.enter(inner);
-        let edges = edges.enter(inner);
-
-        // forward: ((mid1,mid2), (src, len)) can be read as
-        //      src -len-> mid1 -> mid2 is a shortest path from src to mid2.
-        let forward = Variable::new(inner, Product::new(Default::default(), 1));
-        // reverse: ((mid1,mid2), (dst, len)) can be read as
-        //      mid1 -> mid2 -len-> dst is a shortest path from mid1 to dst.
-        let reverse = Variable::new(inner, Product::new(Default::default(), 1));
-
-        // reached((src, dst), (mid1, mid2)) can be read as
-        //      src -*-> mid1 -> mid2 -*-> dst is a shortest path.
-        let reached: Collection<_, ((Id, Id), (Id, Id))> =
-        forward
-            .join_map(&reverse, |&(m1,m2), &(src,len1), &(dst,len2)| {
-                ((src, dst), (len1 + len2, (m1,m2)))
-            })
-            .semijoin(&goals)
-            .reduce(|&_src_dst, source, target| {
-                let min_len = (source[0].0).0;
-                for &(&(len,edge),_wgt) in source.iter() {
-                    if len == min_len {
-                        target.push((edge, 1));
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
                     }
-                }
-            });
-
-        // reached.inspect(|x| println!("\tREACHED\t{:?}", x));
-
-        // Subtract from goals any goal pairs that can reach each other.
-        let active =
-        reached
-            .map(|((src,dst),_mid)| (src,dst))
-            .distinct()
-            .negate()
-            .concat(&goals)
-            .consolidate();
-
-        // Let's expand out forward queries that are active.
-        let forward_active = active.map(|(x, _y)| x).distinct();
-        let forward_next = forward
-            .map(|((_mid0,mid1), (src, len))| (src, (mid1, len)))
-            .semijoin(&forward_active)
-            .map(|(src, (mid1, len))| (mid1, (src, len)))
-            .join_map(&edges, |&mid1, &(src, len), &mid2| {
-                ((mid1,mid2), (src, len + 1))
-            })
-            .concat(&*forward)
-            .map(|((mid1,mid2),(src,len))| ((mid2,src),(len,mid1)))
-            .reduce(|_key, s, t| {
-                let min_len = (s[0].0).0;
-                for (&(len,mid1), _weight) in s.iter() {
-                    if len == min_len {
-                        t.push(((len, mid1), 1));
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
                     }
-                }
-            })
-            .map(|((mid2, src), (len, mid1))| ((mid1,mid2),(src,len)))
-            ;
-
-        // Let's expand out reverse queries that are active.
-        let reverse_active = active.map(|(_x, y)| y).distinct();
-        let reverse_next = reverse
-            .map(|((mid1,_mid2), (rev, len))| (rev, (mid1, len)))
-            .semijoin(&reverse_active)
-            .map(|(rev, (mid1, len))| (mid1, (rev, len)))
-            .join_map(&edges.map(|(x, y)| (y, x)), |&mid1, &(rev, len), &mid0| {
-                ((mid0,mid1), (rev, len + 1))
-            })
-            .concat(&reverse)
-            .map(|((mid0,mid1),(rev,len))| ((mid0,rev),(len,mid1)))
-            // .map(|(edge, (rev, len))| ((edge, rev), len))
-            .reduce(|_key, s, t| {
-                let min_len = (s[0].0).0;
-                for (&(len,mid1), _weight) in s.iter() {
-                    if len == min_len {
-                        t.push(((len, mid1), 1));
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
                     }
-                }
-            })
-            .map(|((mid0, rev), (len, mid1))| ((mid0,mid1), (rev, len)));
-
-        // `reached` are edges on a shortest path;
-        // we want to unwind them back to their roots.
-
-        // reached((src, dst), (mid1, mid2)) means that
-        //      src -*-> mid1 -> mid2 -*-> dst is a shortest path.
-
-        // forward_back(src, dst, )
-        let shortest = Variable::new(inner, Product::new(Default::default(), 1));
-
-        let forward_dag = forward.map(|((mid1,mid2),(src,_len))| ((src,mid2),mid1));
-        let reverse_dag = reverse.map(|((mid1,mid2),(dst,_len))| ((dst,mid1),mid2));
-
-        let short_forward =
-        shortest
-            .map(|((src,dst),(mid1,_mid2))| ((src,mid1),dst))
-            .join_map(&forward_dag, |&(src,mid1),&dst,&mid0| ((src,dst),(mid0,mid1)));
-
-        let short_reverse =
-        shortest
-            .map(|((src,dst),(_mid0,mid1))| ((dst,mid1),src))
-            .join_map(&reverse_dag, |&(dst,mid1),&src,&mid2| ((src,dst),(mid1,mid2)));
-
-        let short =
-        short_forward
-            .concat(&short_reverse)
-            .concat(&reached)
-            .distinct();
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
 
-        shortest.set(&short);
+        // advance and flush all inputs...
+        query_input.advance_to(next_time);
+        query_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        forum_containerof_post_input.advance_to(next_time);
+        forum_containerof_post_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+        forum_input.advance_to(next_time);
+        forum_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
 
-        forward.set(&forward_next.concat(&goals.map(|(x, _)| ((x,x),(x,0)))));
-        reverse.set(&reverse_next.concat(&goals.map(|(_, y)| ((y,y),(y,0)))));
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
 
-        short
-            .filter(|(_,(x,y))| x != y)
-            .leave()
-    })
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
 }
 
 /// Assigns a score to each edge, based on posts and comments.
@@ -0,0 +1,290 @@
+/*
+LDBC SNB BI query 15. Weighted interaction paths
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-15.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Iterate, Reduce, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, weighted_shortest_paths};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+use std::cmp::{min, max};
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_person1 = params[0].parse::<Id>().unwrap();
+    let param_person2 = params[1].parse::<Id>().unwrap();
+    let param_start_ = params[2].clone();
+    let param_end_ = params[3].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_start = parse_datetime(param_start_.clone());
+        let param_end = parse_datetime(param_end_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut knows_input,
+            mut forum_input,
+            mut container_of_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_input, forum) = scope.new_collection::<Forum, _>();
+            let (container_of_input, container_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // posts in forums created in the given time frame
+            let forum_posts = container_of
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> forum_id, post_id
+                .semijoin(
+                    &forum
+                        .filter(move |forum| param_start <= *forum.created() && *forum.created() <= param_end)
+                        .map(|forum| forum.id().clone())
+                )
+                .map(|(_forum_id, post_id)| post_id)
+                ;
+
+            // messages in these forums, where comments belong to the forum of the post of their thread
+            let forum_messages = forum_posts
+                .iterate(|transitive| {
+                    let posts = forum_posts.enter(&transitive.scope());
+                    let replies = reply_of
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> parent_id, reply_id
+                        .enter(&transitive.scope());
+
+                    transitive
+                        .map(|message_id| (message_id, ()))
+                        .join_map(&replies, |_parent_id, _dummy, reply_id| reply_id.clone())
+                        .concat(&posts)
+                        .distinct()
+                })
+                ;
+
+            // replies in these forums are worth 1 point to a post, and 0.5 points to a comment.
+            // points are doubled, to keep them integers.
+            let replies = reply_of
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> comment_id, parent_id
+                .semijoin(&forum_messages)
+                .map(|(comment_id, parent_id)| (parent_id, comment_id))
+                ;
+            let reply_points = replies
+                .semijoin(&forum_posts)
+                .map(|(parent_id, comment_id)| (parent_id, (comment_id, 2)))
+                .concat(
+                    &replies
+                        .antijoin(&forum_posts)
+                        .map(|(parent_id, comment_id)| (parent_id, (comment_id, 1)))
+                )
+                ; // -> (parent_id, (comment_id, points))
+
+            let creators = has_creator
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> message_id, person_id
+                ;
+
+            // interactions between the creators of the replies and their parents
+            let interactions = reply_points
+                .join_map(&creators, |_parent_id, &(comment_id, points), &parent_creator| (comment_id, (parent_creator, points)))
+                .join_map(&creators, |_comment_id, &(parent_creator, points), &creator|
+                    ((min(creator, parent_creator), max(creator, parent_creator)), points)
+                )
+                ; // -> ((person1_id, person2_id), points)
+
+            // knows edges with the lower id first
+            let edges = knows
+                .map(|conn| (min(conn.a().clone(), conn.b().clone()), max(conn.a().clone(), conn.b().clone())))
+                ;
+
+            // the weight of a knows edge is 1 / (interaction score + 1)
+            let weights = interactions
+                .semijoin(&edges)
+                .concat(
+                    &edges.map(|edge| (edge, 0))
+                ) // make sure to have each knows edge, even without interactions
+                .reduce(|_edge, input, output| {
+                    let mut sum: isize = 0;
+                    for (points, diff) in input.iter() {
+                        sum += **points * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                .map(
+                    // fixme: hack solution as floats cannot be used directly (not implementing Ord)
+                    |((p1, p2), points)| (p1, (p2, ((2.0 / (points + 2) as f64) * 10000000000.0) as isize))
+                )
+                ; // -> (src, (dst, weight))
+
+            // create bidirectional weights
+            let weights = weights
+                .concat(&weights.map(|(p1, (p2, weight))| (p2, (p1, weight))))
+                ;
+
+            // the single (person1, person2) goal, as a collection to be joined with the paths
+            let goals = edges
+                .map(move |_edge| (param_person1, param_person2))
+                .distinct()
+                ;
+
+            let result = weighted_shortest_paths(&weights, &goals)
+                .map(|(_goal, (path, weight))| (
+                    (weight, path.clone()), // sort: +weight, +path
+                    vec![
+                        path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(";"),
+                        (weight as f64 / 10000000000.0).to_string()
+                    ]
+                ))
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                knows_input, forum_input, container_of_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        input_insert_vec(load_forum(path.as_str(), index, peers), &mut forum_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_containerOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut container_of_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        forum_input.advance_to(next_time);
+        forum_input.flush();
+        container_of_input.advance_to(next_time);
+        container_of_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
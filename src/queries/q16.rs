@@ -0,0 +1,302 @@
+/*
+LDBC SNB BI query 16. Fake news detection
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-16.pdf
+*/
+use timely::dataflow::{ProbeHandle, Scope};
+use differential_dataflow::Collection;
+use differential_dataflow::input::Input;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::{Join, Count, Threshold};
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, tagged_messages, DAY};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_a_ = params[0].clone();
+    let param_date_a_ = params[1].clone();
+    let param_tag_b_ = params[2].clone();
+    let param_date_b_ = params[3].clone();
+    let param_max_knows = params[4].parse::<isize>().unwrap();
+    let param_max_repliers = params[5].parse::<isize>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag_a = param_tag_a_.clone();
+        let param_date_a = parse_datetime(param_date_a_.clone());
+        let param_tag_b = param_tag_b_.clone();
+        let param_date_b = parse_datetime(param_date_b_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut knows_input,
+            mut has_tag_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            let suspects_a = suspects(
+                &tag, &has_tag, &has_creator, &reply_of, &bi_knows,
+                param_tag_a, param_date_a, param_max_knows, param_max_repliers
+            );
+            let suspects_b = suspects(
+                &tag, &has_tag, &has_creator, &reply_of, &bi_knows,
+                param_tag_b, param_date_b, param_max_knows, param_max_repliers
+            );
+
+            let result = suspects_a
+                .join(&suspects_b)
+                .map(|(person_id, (count_a, count_b))| (
+                    (std::isize::MAX - (count_a + count_b), person_id), // sort: -(count_a + count_b), +person_id
+                    vec![person_id.to_string(), count_a.to_string(), count_b.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, knows_input, has_tag_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                "message-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
+
+/// Finds the persons who created messages with the Tag called `tag_name` on the day of `date`.
+///
+/// A person is only kept if they know at most `max_knows` of the other such persons, and
+/// their messages got replies from at most `max_repliers` distinct other persons.
+/// The result is a collection of (person_id, message_count).
+fn suspects<G>(
+    tag: &Collection<G, Tag>,
+    has_tag: &Collection<G, DynamicConnection>,
+    has_creator: &Collection<G, DynamicConnection>,
+    reply_of: &Collection<G, DynamicConnection>,
+    bi_knows: &Collection<G, (Id, Id)>,
+    tag_name: String,
+    date: Date,
+    max_knows: isize,
+    max_repliers: isize,
+) -> Collection<G, (Id, isize)>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Ord,
+{
+    // hasTag relations are created at the same time as the message itself.
+    let messages = tagged_messages(
+        tag,
+        &has_tag.filter(move |conn| date <= *conn.created() && *conn.created() < date + DAY),
+        has_creator,
+        tag_name
+    ); // -> (message_id, creator_id)
+
+    let message_counts = messages
+        .map(|(_message_id, creator_id)| creator_id)
+        .count()
+        ; // -> (person_id, message_count)
+
+    let persons = message_counts.map(|(person_id, _count)| person_id);
+
+    // persons knowing too many of the other persons
+    let too_connected = bi_knows
+        .semijoin(&persons)
+        .map(|(person_id, friend_id)| (friend_id, person_id))
+        .semijoin(&persons)
+        .map(|(_friend_id, person_id)| person_id)
+        .count()
+        .filter(move |(_person_id, friends)| *friends > max_knows)
+        .map(|(person_id, _friends)| person_id)
+        ;
+
+    // persons whose messages were replied by too many distinct other persons
+    let too_replied = messages
+        .join_map(
+            &reply_of.map(|conn| (conn.b().clone(), conn.a().clone())), // -> parent_id, reply_id
+            |_message_id, creator_id, reply_id| (reply_id.clone(), creator_id.clone())
+        )
+        .join_map(
+            &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+            |_reply_id, creator_id, replier_id| (creator_id.clone(), replier_id.clone())
+        )
+        .filter(|(creator_id, replier_id)| creator_id != replier_id)
+        .distinct()
+        .map(|(creator_id, _replier_id)| creator_id)
+        .count()
+        .filter(move |(_person_id, repliers)| *repliers > max_repliers)
+        .map(|(person_id, _repliers)| person_id)
+        ;
+
+    message_counts
+        .antijoin(&too_connected)
+        .antijoin(&too_replied)
+}
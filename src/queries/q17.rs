@@ -0,0 +1,292 @@
+/*
+LDBC SNB BI query 17. Information propagation analysis
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-17.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Iterate, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_ = params[0].clone();
+    let param_delta = params[1].parse::<Date>().unwrap() * 60 * 60; // hours -> seconds
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag = param_tag_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut forum_hasmember_input,
+            mut forum_containerof_post_input,
+            mut has_tag_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (forum_hasmember_input, forum_hasmember) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_containerof_post_input, forum_containerof_post) = scope.new_collection::<DynamicConnection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            // forum of each message, which is the forum of the post at the root of its thread
+            let posts = forum_containerof_post
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> post_id, forum_id
+                ;
+            let message_forums = posts
+                .iterate(|transitive| {
+                    let posts = posts.enter(&transitive.scope());
+                    let replies = reply_of
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> parent_id, reply_id
+                        .enter(&transitive.scope());
+
+                    transitive
+                        .join_map(&replies, |_parent_id, forum_id, reply_id| (reply_id.clone(), forum_id.clone()))
+                        .concat(&posts)
+                        .distinct()
+                })
+                ; // -> (message_id, forum_id)
+
+            // messages with the given Tag, with their creation date, creator and forum.
+            // hasTag relations are created at the same time as the message itself.
+            let messages = has_tag
+                .map(|conn| (conn.b().clone(), (conn.a().clone(), conn.created().clone()))) // -> tag_id, (message_id, created)
+                .semijoin(
+                    &tag
+                        .filter(move |x| param_tag.eq(x.name()))
+                        .map(|tag| tag.id().clone())
+                )
+                .map(|(_tag_id, message)| message)
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |message_id, created, creator_id| (message_id.clone(), (created.clone(), creator_id.clone()))
+                )
+                .join_map(
+                    &message_forums,
+                    |message_id, &(created, creator_id), forum_id| (message_id.clone(), created, creator_id, forum_id.clone())
+                )
+                ; // -> (message_id, created, creator_id, forum_id)
+
+            // replies among these messages
+            let replies = messages
+                .map(|(message_id, created, creator_id, forum_id)| (message_id, (created, creator_id, forum_id)))
+                .semijoin(&reply_of.map(|conn| conn.a().clone()))
+                .map(|(reply_id, (created, creator_id, forum_id))| (creator_id, (reply_id, created, forum_id)))
+                ; // -> (creator_id, (reply_id, created, forum_id))
+
+            let memberships = forum_hasmember
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> forum_id, person_id
+                ;
+
+            // person1 created a message in forum1, and person2 is a member of forum1. person2 later replied
+            // in forum2, where person1 is a member, within delta hours after the message of person1.
+            let propagations = messages
+                .map(|(_message_id, created, creator_id, forum_id)| (forum_id, (created, creator_id)))
+                .join_map(
+                    &memberships,
+                    |forum1, &(created1, person1), person2| (person2.clone(), (forum1.clone(), created1, person1))
+                )
+                .join_map(
+                    &replies,
+                    |person2, &(forum1, created1, person1), &(reply_id, created2, forum2)|
+                        ((forum2, person1), (forum1, created1, person2.clone(), reply_id, created2))
+                )
+                .filter(move |((forum2, person1), (forum1, created1, person2, _reply_id, created2))|
+                    forum1 != forum2 && person1 != person2
+                        && created1 < created2 && *created2 <= created1 + param_delta
+                )
+                .semijoin(&memberships)
+                .map(|((_forum2, person1), (_forum1, _created1, person2, reply_id, _created2))| ((person1, person2), reply_id))
+                .distinct()
+                ; // -> ((person1_id, person2_id), reply_id)
+
+            let result = propagations
+                .map(|(pair, _reply_id)| pair)
+                .count()
+                .map(|((person1, person2), count)| (
+                    (std::isize::MAX - count, person1, person2), // sort: -count, +person1_id, +person2_id
+                    vec![person1.to_string(), person2.to_string(), count.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 10)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, forum_hasmember_input, forum_containerof_post_input,
+                has_tag_input, has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_forum_has_member(path.as_str(), index, peers), &mut forum_hasmember_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_containerOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut forum_containerof_post_input,
+            next_time
+        );
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "forum-hasmember-person" => {
+                    if create {
+                        forum_hasmember_input.insert(d);
+                    } else {
+                        forum_hasmember_input.remove(d);
+                    }
+                },
+                "forum-containerof-post" => {
+                    if create {
+                        forum_containerof_post_input.insert(d);
+                    } else {
+                        forum_containerof_post_input.remove(d);
+                    }
+                },
+                "message-hastag-tag" => {
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                "message-hascreator-person" => {
+                    if create {
+                        has_creator_input.insert(d);
+                    } else {
+                        has_creator_input.remove(d);
+                    }
+                },
+                "comment-replyof-message" => {
+                    if create {
+                        reply_of_input.insert(d);
+                    } else {
+                        reply_of_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        forum_hasmember_input.advance_to(next_time);
+        forum_hasmember_input.flush();
+        forum_containerof_post_input.advance_to(next_time);
+        forum_containerof_post_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,181 @@
+/*
+LDBC SNB BI query 18. Friend recommendation
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-18.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag = param_tag_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut has_interest_input,
+            mut knows_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (has_interest_input, has_interest) = scope.new_collection::<DynamicConnection, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons interested in the given Tag
+            let interested = has_interest
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, person_id
+                .semijoin(
+                    &tag
+                        .filter(move |x| param_tag.eq(x.name()))
+                        .map(|tag| tag.id().clone())
+                )
+                .map(|(_tag_id, person_id)| person_id)
+                ;
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // friends of the interested persons
+            let friends = bi_knows
+                .semijoin(&interested)
+                .map(|(person_id, friend_id)| (friend_id, person_id))
+                ; // -> (friend_id, person_id)
+
+            // pairs of interested persons who do not know each other, with their mutual friends
+            let recommendations = friends
+                .join_map(&friends, |mutual_friend, person1, person2| ((person1.clone(), person2.clone()), mutual_friend.clone()))
+                .filter(|((person1, person2), _mutual_friend)| person1 != person2)
+                .antijoin(&bi_knows)
+                ; // -> ((person1_id, person2_id), mutual_friend_id)
+
+            let result = recommendations
+                .map(|(pair, _mutual_friend)| pair)
+                .count()
+                .map(|((person1, person2), count): ((Id, Id), isize)| (
+                    (std::isize::MAX - count, person1, person2), // sort: -count, +person1_id, +person2_id
+                    vec![person1.to_string(), person2.to_string(), count.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, has_interest_input, knows_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_person_has_interest(path.as_str(), index, peers), &mut has_interest_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-hasinterest-tag" => {
+                    if create {
+                        has_interest_input.insert(d);
+                    } else {
+                        has_interest_input.remove(d);
+                    }
+                },
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        has_interest_input.advance_to(next_time);
+        has_interest_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
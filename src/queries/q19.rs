@@ -3,16 +3,15 @@ LDBC SNB BI query 19. Interaction path between cities
 https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-19.pdf
 */
 
-use differential_dataflow::collection::AsCollection;
 use differential_dataflow::input::Input;
-use differential_dataflow::operators::{Join, Count, Iterate, Reduce};
+use differential_dataflow::operators::{Join, Count};
 use timely::dataflow::ProbeHandle;
 
 use crate::lib::loader::*;
 use crate::lib::types::*;
-use crate::lib::helpers::{input_insert_vec, limit, print_trace};
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, weighted_distances};
 use differential_dataflow::operators::arrange::ArrangeBySelf;
-use timely::dataflow::operators::{Probe, Map, Delay};
+use timely::dataflow::operators::Probe;
 use std::time::Instant;
 use std::cmp::{min, max};
 
@@ -102,40 +101,8 @@ pub fn run(path: String, change_path: String, params: &Vec<String>) {
                 .concat(&weights.map(|(p1, (p2, c))| (p2, (p1, c))))
                 ;
 
-            // root nodes are people from city one.
-            // ((p, p) 0) represents an initial path from p to p with 0 weight.
-            let nodes = people1
-                .map(|p| ((p, p), 0))
-                ;
-
             // calculate shortest paths from people in city 1
-            // based on https://github.com/frankmcsherry/blog/blob/master/posts/2019-05-20.md
-            let shortest_paths = nodes
-                .iterate(|dists| { // calculate shortest path to every other node
-                    let edges = weights.enter(&dists.scope());
-                    let nodes = nodes.enter(&dists.scope());
-
-                    dists // -> ((src, dst), distance)
-                        .map(|((root, dst), dist)| (dst.clone(), (root.clone(), dist.clone())))
-                        // join next step and calculate weights
-                        .join_map(
-                            &edges,
-                            |_src, (root, distance), (dst, weight)| ((root.clone(), dst.clone()), distance + weight)
-                        ) // -> ((root, dst), distance),
-                        // add original nodes
-                        .concat(&nodes) // -> ((root, dst), distance)
-
-                        // Timely magic, to speed up updates with some time manipulation (see blogpost above)
-                        .inner
-                        .map_in_place(|((_d, w), t, _r)|
-                            t.inner = std::cmp::max(t.inner, *w as u64)
-                        )
-                        .delay(|(_, t, _), _| t.clone())
-                        .as_collection()
-
-                        // finally keep only the shortest path between two nodes (grouped by (src, dst))
-                        .reduce(|_key, input, output| output.push((*input[0].0, 1)))
-                }) // -> ((src, dst), distance)
+            let shortest_paths = weighted_distances(&weights, &people1) // -> ((src, dst), distance)
                 // map for semijoin
                 .map(|((src, dst), distance)| (dst, (src, distance)))
                 // filter out result which are not between people from city1 and city2
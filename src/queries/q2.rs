@@ -0,0 +1,205 @@
+/*
+LDBC SNB BI query 2. Tag evolution
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-02.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Reduce};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, date_bucket, DAY};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_date_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_date = parse_datetime(param_date_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut has_tag_input,
+            mut post_input,
+            mut comment_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+
+            let (post_input, post) = scope.new_collection::<Post, _>();
+            let (comment_input, comment) = scope.new_collection::<Comment, _>();
+
+            // collect messages created in one of the two 100 day long windows starting at the given date
+            let messages = post
+                .map(|post| (post.id().clone(), post.created().clone()))
+                .concat(
+                    &comment.map(|comment| (comment.id().clone(), comment.created().clone()))
+                )
+                .map(move |(message_id, created)| (message_id, date_bucket(created, param_date, 100 * DAY)))
+                .filter(|(_message_id, window)| *window == 0 || *window == 1)
+                ; // -> (message_id, window)
+
+            // count the messages in both windows for each Tag
+            let counts = messages
+                .join_map(
+                    &has_tag.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, tag_id
+                    |_message_id, window, tag_id| (tag_id.clone(), window.clone())
+                )
+                .reduce(|_tag_id, input, output| {
+                    let mut counts: (isize, isize) = (0, 0);
+                    for (window, diff) in input.iter() {
+                        if **window == 0 {
+                            counts.0 += diff;
+                        } else {
+                            counts.1 += diff;
+                        }
+                    }
+                    output.push((counts, 1));
+                })
+                ; // -> (tag_id, (count_window1, count_window2))
+
+            let result = counts
+                .join_map(
+                    &tag.map(|tag| (tag.id().clone(), tag.name().clone())),
+                    |_tag_id, &(count1, count2), name| {
+                        let diff = (count1 - count2).abs();
+                        (
+                            (std::isize::MAX - diff, name.clone()), // sort: -diff, +name
+                            vec![name.to_string(), count1.to_string(), count2.to_string(), diff.to_string()]
+                        )
+                    }
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, has_tag_input, post_input, comment_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_post(path.as_str(), index, peers), &mut post_input, next_time);
+        input_insert_vec(load_comment(path.as_str(), index, peers), &mut comment_input, next_time);
+
+        // insert hasTag relations both for posts and comments, to handle them together in the dataflow
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "post" => {
+                    let post = parse_post(change_row);
+                    if create {
+                        post_input.insert(post);
+                    } else {
+                        post_input.remove(post);
+                    }
+                },
+                "comment" => {
+                    let comment = parse_comment(change_row);
+                    if create {
+                        comment_input.insert(comment);
+                    } else {
+                        comment_input.remove(comment);
+                    }
+                },
+                "comment-hastag-tag" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        has_tag_input.insert(d);
+                    } else {
+                        has_tag_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        post_input.advance_to(next_time);
+        post_input.flush();
+        comment_input.advance_to(next_time);
+        comment_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -0,0 +1,208 @@
+/*
+LDBC SNB BI query 20. Recruitment
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-20.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Reduce, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, weighted_distances};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_company_ = params[0].clone();
+    let param_person2 = params[1].parse::<Id>().unwrap();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_company = param_company_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut organisation_input,
+            mut study_at_input,
+            mut work_at_input,
+            mut knows_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (organisation_input, organisation) = scope.new_collection::<Organisation, _>();
+            let (study_at_input, study_at) = scope.new_collection::<StudyAt, _>();
+            let (work_at_input, work_at) = scope.new_collection::<WorkAt, _>();
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+
+            // persons working at the given company
+            let employees = work_at
+                .map(|work_at| (work_at.company().clone(), work_at.person().clone())) // -> company_id, person_id
+                .semijoin(
+                    &organisation
+                        .filter(move |x| param_company.eq(x.name()))
+                        .map(|organisation| organisation.id().clone())
+                )
+                .map(|(_company_id, person_id)| person_id)
+                .distinct()
+                ;
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // persons who know each other and studied at the same university are connected.
+            // the weight of the edge is the smallest difference of their class years plus one.
+            let edges = bi_knows
+                .join_map(
+                    &study_at.map(|study_at| (study_at.person().clone(), (study_at.university().clone(), study_at.class_year().clone()))),
+                    |&person1, &person2, &(university_id, year1)| ((person2, university_id), (person1, year1))
+                ) // -> ((person2_id, university_id), (person1_id, class_year1))
+                .join_map(
+                    &study_at.map(|study_at| ((study_at.person().clone(), study_at.university().clone()), study_at.class_year().clone())),
+                    |&(person2, _university_id), &(person1, year1), &year2|
+                        ((person1, person2), ((year1 - year2).abs() + 1) as isize)
+                )
+                .reduce(|_pair, input, output| output.push((*input[0].0, 1)))
+                .map(|((person1, person2), weight)| (person1, (person2, weight)))
+                ; // -> (src, (dst, weight))
+
+            // person2 is the only root of the search
+            let root = bi_knows
+                .filter(move |(person, _friend)| param_person2.eq(person))
+                .map(|(person, _friend)| person)
+                .distinct()
+                ;
+
+            // the graph is undirected, so the distances from person2 are the distances to it
+            let result = weighted_distances(&edges, &root)
+                .map(|((_root, person1), distance)| (person1, distance))
+                .filter(move |(person1, _distance)| *person1 != param_person2)
+                .semijoin(&employees)
+                .map(|(person1, distance)| (
+                    (distance, person1), // sort: +distance, +person1_id
+                    vec![person1.to_string(), distance.to_string()]
+                ))
+                ;
+
+            let arrangement = limit(&result, 20)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                organisation_input, study_at_input, work_at_input, knows_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_organisation(path.as_str(), index, peers), &mut organisation_input, next_time);
+        input_insert_vec(load_study_at(path.as_str(), index, peers), &mut study_at_input, next_time);
+        input_insert_vec(load_work_at(path.as_str(), index, peers), &mut work_at_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(knows_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            match input.as_str() {
+                "person-studyat-organisation" => {
+                    let study_at = parse_study_at(change_row);
+                    if create {
+                        study_at_input.insert(study_at);
+                    } else {
+                        study_at_input.remove(study_at);
+                    }
+                },
+                "person-workat-organisation" => {
+                    let work_at = parse_work_at(change_row);
+                    if create {
+                        work_at_input.insert(work_at);
+                    } else {
+                        work_at_input.remove(work_at);
+                    }
+                },
+                "person-knows-person" => {
+                    let mut row_iter = change_row.into_iter();
+                    let created = parse_datetime(row_iter.next().unwrap());
+                    let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+                    let d = DynamicConnection::new(created, id1, id2);
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        organisation_input.advance_to(next_time);
+        organisation_input.flush();
+        study_at_input.advance_to(next_time);
+        study_at_input.flush();
+        work_at_input.advance_to(next_time);
+        work_at_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
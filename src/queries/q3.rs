@@ -12,7 +12,7 @@ use timely::dataflow::operators::probe::Probe;
 
 use crate::lib::loader::*;
 use crate::lib::types::*;
-use crate::lib::helpers::{limit, format_timestamp, input_insert_vec, print_trace};
+use crate::lib::helpers::{limit, format_timestamp, input_insert_vec, print_trace, country_cities, tag_class_tags};
 use std::time::Instant;
 
 pub fn run(path: String, change_path: String, params: &Vec<String>) {
@@ -56,15 +56,7 @@ pub fn run(path: String, change_path: String, params: &Vec<String>) {
             let (forum_hasmod_input, forum_hasmod) = scope.new_collection::<DynamicConnection, _>();
             let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
 
-            let tag_ids = tag_classes
-                // filter for selected TagClass
-                .filter(move |x| param_tag_class.eq(x.name()))
-                .map(|tag| (tag.id().clone(), ()))
-                // join Tag based on TagClass
-                .join_map(
-                    &tag_hastype_tagclass.map(|conn| (conn.b().clone(), conn.a().clone())),
-                    |_tag_class_id, _dummy, c| c.clone(),
-                );
+            let tag_ids = tag_class_tags(&tag_classes, &tag_hastype_tagclass, param_tag_class);
 
             // get post count for forums, which contain the desired tagclass
             // (forum_id, (count, title, created))
@@ -84,13 +76,7 @@ pub fn run(path: String, change_path: String, params: &Vec<String>) {
                         (forum_id.clone(), (*count, forum_data.0.clone(), forum_data.1.clone()))
                 );
 
-            let cities = place
-                .filter(move |x| param_country.eq(x.name()))
-                .map(|x| (x.id().clone(), ()))
-                .join_map(
-                    &place_is_part_of_place.map(|x| (x.b().clone(), x.a().clone())),
-                    |_country, _dummy, city| city.clone()
-                );
+            let cities = country_cities(&place, &place_is_part_of_place, param_country);
 
             let forums_in_cities = forum_hasmod
                 .map(|forum_mod| (forum_mod.b().clone(), forum_mod.a().clone())) // -> (person.id, forum.id)
@@ -0,0 +1,288 @@
+/*
+LDBC SNB BI query 4. Top message creators in a country
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-04.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Count, Iterate, Threshold};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, format_timestamp, country_cities};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_country_ = params[0].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_country = param_country_.clone();
+
+        // create dataflow
+        let (
+            mut trace,
+            mut person_input,
+            mut place_input,
+            mut place_is_part_of_place_input,
+            mut located_in_input,
+            mut forum_hasmod_input,
+            mut forum_hasmember_input,
+            mut forum_containerof_post_input,
+            mut has_creator_input,
+            mut reply_of_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (person_input, person) = scope.new_collection::<Person, _>();
+            let (place_input, place) = scope.new_collection::<Place, _>();
+            let (place_is_part_of_place_input, place_is_part_of_place) = scope.new_collection::<Connection, _>();
+            let (located_in_input, located_in) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_hasmod_input, forum_hasmod) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_hasmember_input, forum_hasmember) = scope.new_collection::<DynamicConnection, _>();
+            let (forum_containerof_post_input, forum_containerof_post) = scope.new_collection::<DynamicConnection, _>();
+
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+            // replyOf for comments AND posts
+            let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
+
+            let cities = country_cities(&place, &place_is_part_of_place, param_country);
+
+            // forums moderated by a person living in the given country
+            let forums_in_country = forum_hasmod
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> person_id, forum_id
+                .join_map(
+                    &located_in.map(|conn| (conn.a().clone(), conn.b().clone())), // -> person_id, place_id
+                    |_person_id, forum_id, place_id| (place_id.clone(), forum_id.clone())
+                )
+                .semijoin(&cities)
+                .map(|(_place_id, forum_id)| forum_id)
+                ;
+
+            // top 100 forums of the country by member count
+            let memberships = forum_hasmember
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> forum_id, person_id
+                ;
+            let top_forums = limit(
+                &memberships
+                    .semijoin(&forums_in_country)
+                    .map(|(forum_id, _person_id)| forum_id)
+                    .count()
+                    .map(|(forum_id, count)| ((std::isize::MAX - count, forum_id), forum_id)), // sort: -count, +forum_id
+                100
+            )
+                .flat_map(|forum_ids| forum_ids.into_iter())
+                ; // -> forum_id
+
+            // persons who are members of any of the top forums
+            let members = memberships
+                .semijoin(&top_forums)
+                .map(|(_forum_id, person_id)| person_id)
+                .distinct()
+                ;
+
+            // posts of the top forums
+            let posts = forum_containerof_post
+                .map(|conn| (conn.a().clone(), conn.b().clone())) // -> forum_id, post_id
+                .semijoin(&top_forums)
+                .map(|(_forum_id, post_id)| post_id)
+                ;
+
+            // messages of the top forums, which are the posts and all their (transitive) replies
+            let messages = posts
+                .iterate(|transitive| {
+                    let posts = posts.enter(&transitive.scope());
+                    let replies = reply_of
+                        .map(|conn| (conn.b().clone(), conn.a().clone())) // -> parent_id, reply_id
+                        .enter(&transitive.scope());
+
+                    transitive
+                        .map(|message_id| (message_id, ()))
+                        .join_map(&replies, |_parent_id, _dummy, reply_id| reply_id.clone())
+                        .concat(&posts)
+                        .distinct()
+                })
+                ;
+
+            // count messages of each member,
+            // members are added once more to have an outer join, which is corrected later
+            let message_counts = messages
+                .map(|message_id| (message_id, ()))
+                .join_map(
+                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
+                    |_message_id, _dummy, person_id| (person_id.clone(), ())
+                )
+                .semijoin(&members)
+                .map(|(person_id, _dummy)| person_id)
+                .concat(&members)
+                .count()
+                ; // -> person_id, message_count + 1
+
+            let result = message_counts
+                .join_map(
+                    &person.map(|person| (
+                        person.id().clone(),
+                        (person.first_name().clone(), person.last_name().clone(), person.created().clone())
+                    )),
+                    |person_id, count, (first_name, last_name, created)| (
+                        (std::isize::MAX - (count - 1), person_id.clone()), // sort: -count, +person_id
+                        vec![
+                            person_id.to_string(), first_name.to_string(), last_name.to_string(),
+                            format_timestamp(*created as u64), (count - 1).to_string()
+                        ]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                person_input, place_input, place_is_part_of_place_input, located_in_input,
+                forum_hasmod_input, forum_hasmember_input, forum_containerof_post_input,
+                has_creator_input, reply_of_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_person(path.as_str(), index, peers), &mut person_input, next_time);
+        input_insert_vec(load_place(path.as_str(), index, peers), &mut place_input, next_time);
+        input_insert_vec(
+            load_connection("static/place_isPartOf_place_0_0.csv", path.as_str(), index, peers),
+            &mut place_is_part_of_place_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_isLocatedIn_place_0_0.csv", path.as_str(), index, peers),
+            &mut located_in_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_hasModerator_person_0_0.csv", path.as_str(), index, peers),
+            &mut forum_hasmod_input,
+            next_time
+        );
+        input_insert_vec(load_forum_has_member(path.as_str(), index, peers), &mut forum_hasmember_input, next_time);
+        input_insert_vec(
+            load_dynamic_connection("dynamic/forum_containerOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut forum_containerof_post_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        // insert replyOf relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_post_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_replyOf_comment_0_0.csv", path.as_str(), index, peers),
+            &mut reply_of_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(person_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "forum-hasmember-person" => {
+                    if create {
+                        forum_hasmember_input.insert(d);
+                    } else {
+                        forum_hasmember_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        person_input.advance_to(next_time);
+        person_input.flush();
+        place_input.advance_to(next_time);
+        place_input.flush();
+        place_is_part_of_place_input.advance_to(next_time);
+        place_is_part_of_place_input.flush();
+        located_in_input.advance_to(next_time);
+        located_in_input.flush();
+        forum_hasmod_input.advance_to(next_time);
+        forum_hasmod_input.flush();
+        forum_hasmember_input.advance_to(next_time);
+        forum_hasmember_input.flush();
+        forum_containerof_post_input.advance_to(next_time);
+        forum_containerof_post_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        reply_of_input.advance_to(next_time);
+        reply_of_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}
@@ -8,7 +8,7 @@ use timely::dataflow::ProbeHandle;
 
 use crate::lib::loader::*;
 use crate::lib::types::*;
-use crate::lib::helpers::{input_insert_vec, limit, print_trace};
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, tagged_messages};
 use differential_dataflow::operators::arrange::ArrangeBySelf;
 use timely::dataflow::operators::Probe;
 use std::time::Instant;
@@ -48,21 +48,8 @@ pub fn run(path: String, change_path: String, params: &Vec<String>) {
             // replyOf for comments AND posts
             let (reply_of_input, reply_of) = scope.new_collection::<DynamicConnection, _>();
 
-            // get the id of the given Tag.
-            let needed_tag = tag
-                .filter(move |x| param_tag.eq(x.name()))
-                .map(|tag| tag.id().clone());
-
             // collect all the messages (posts and comments) which were created with the given Tag
-            let messages = has_tag
-                .map(|conn| (conn.b().clone(), conn.a().clone()))
-                .semijoin(&needed_tag)
-                .map(|(_tagid, messageid)| (messageid, ()))
-                .join_map(
-                    &has_creator.map(|conn| (conn.a().clone(), conn.b().clone())), // -> message_id, person_id
-                    |message_id, _dummy, person_id| (message_id.clone(), person_id.clone())
-                )
-                ; // -> (message_id, creator_id)
+            let messages = tagged_messages(&tag, &has_tag, &has_creator, param_tag); // -> (message_id, creator_id)
 
             // count the messages per person
             let score_messages = messages
@@ -152,12 +139,12 @@ pub fn run(path: String, change_path: String, params: &Vec<String>) {
         );
         // insert likes relations
         input_insert_vec(
-            load_dynamic_connection("dynamic/person_likes_post_0_0.csv", path.as_str(), index, peers),
+            load_person_likes_post(path.as_str(), index, peers),
             &mut likes_input,
             0 // do not advance just yet
         );
         input_insert_vec(
-            load_dynamic_connection("dynamic/person_likes_comment_0_0.csv", path.as_str(), index, peers),
+            load_person_likes_comment(path.as_str(), index, peers),
             &mut likes_input,
             next_time
         );
@@ -0,0 +1,249 @@
+/*
+LDBC SNB BI query 8. Central person for a tag
+https://ldbc.github.io/ldbc_snb_docs_snapshot/bi-read-08.pdf
+*/
+use differential_dataflow::input::Input;
+use differential_dataflow::operators::{Join, Reduce};
+use timely::dataflow::ProbeHandle;
+
+use crate::lib::loader::*;
+use crate::lib::types::*;
+use crate::lib::helpers::{input_insert_vec, limit, print_trace, tagged_messages};
+use differential_dataflow::operators::arrange::ArrangeBySelf;
+use timely::dataflow::operators::Probe;
+use std::time::Instant;
+
+pub fn run(path: String, change_path: String, params: &Vec<String>) {
+    // unpack parameters
+    let param_tag_ = params[0].clone();
+    let param_start_ = params[1].clone();
+    let param_end_ = params[2].clone();
+
+    timely::execute_from_args(std::env::args(), move |worker| {
+        let mut timer = worker.timer();
+        let index = worker.index();
+        let peers = worker.peers();
+
+        let mut probe = ProbeHandle::new();
+
+        // bind parameters
+        let param_tag = param_tag_.clone();
+        let param_start = parse_datetime(param_start_.clone());
+        let param_end = parse_datetime(param_end_.clone());
+
+        // create dataflow
+        let (
+            mut trace,
+            mut tag_input,
+            mut has_interest_input,
+            mut has_tag_input,
+            mut has_creator_input,
+            mut knows_input,
+        ) =
+        worker.dataflow::<usize,_,_>(|scope| {
+            let (tag_input, tag) = scope.new_collection::<Tag, _>();
+            let (has_interest_input, has_interest) = scope.new_collection::<DynamicConnection, _>();
+
+            // tags for comments AND posts
+            let (has_tag_input, has_tag) = scope.new_collection::<DynamicConnection, _>();
+            // creators for comments AND posts
+            let (has_creator_input, has_creator) = scope.new_collection::<DynamicConnection, _>();
+
+            let (knows_input, knows) = scope.new_collection::<DynamicConnection, _>();
+
+            // each message with the given Tag, created in the given time frame, worth 1 point for its creator.
+            // hasTag relations are created at the same time as the message itself.
+            let message_scores = tagged_messages(
+                &tag,
+                &has_tag.filter(move |conn| param_start <= *conn.created() && *conn.created() <= param_end),
+                &has_creator,
+                param_tag.clone()
+            )
+                .map(|(_message_id, creator_id)| (creator_id, 1))
+                ;
+
+            // persons interested in the given Tag get 100 points
+            let interest_scores = has_interest
+                .map(|conn| (conn.b().clone(), conn.a().clone())) // -> tag_id, person_id
+                .semijoin(
+                    &tag
+                        .filter(move |x| param_tag.eq(x.name()))
+                        .map(|tag| tag.id().clone())
+                )
+                .map(|(_tag_id, person_id)| (person_id, 100))
+                ;
+
+            let scores = interest_scores
+                .concat(&message_scores)
+                .reduce(|_person_id, input, output| {
+                    let mut sum: isize = 0;
+                    for (score, diff) in input.iter() {
+                        sum += **score * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                ; // -> (person_id, score)
+
+            // bidirectional knows relation
+            let bi_knows = knows
+                .map(|conn| (conn.b().clone(), conn.a().clone()))
+                .concat(
+                    &knows.map(|conn| (conn.a().clone(), conn.b().clone()))
+                )
+                ;
+
+            // sum the scores of the friends of each scored person
+            let friends_scores = bi_knows
+                .join_map(
+                    &scores,
+                    |_friend_id, person_id, score| (person_id.clone(), score.clone())
+                )
+                .semijoin(&scores.map(|(person_id, _score)| person_id))
+                .concat(
+                    &scores.map(|(person_id, _score)| (person_id, 0))
+                ) // make sure to have each person, even without scored friends
+                .reduce(|_person_id, input, output| {
+                    let mut sum: isize = 0;
+                    for (score, diff) in input.iter() {
+                        sum += **score * diff;
+                    }
+                    output.push((sum, 1));
+                })
+                ; // -> (person_id, friends_score)
+
+            let result = scores
+                .join_map(
+                    &friends_scores,
+                    |person_id, score, friends_score| (
+                        (std::isize::MAX - (score + friends_score), person_id.clone()), // sort: -(score + friendsScore), +person_id
+                        vec![person_id.to_string(), score.to_string(), friends_score.to_string()]
+                    )
+                )
+                ;
+
+            let arrangement = limit(&result, 100)
+                .arrange_by_self();
+
+            arrangement.stream.probe_with(&mut probe);
+
+            return (
+                arrangement.trace,
+                tag_input, has_interest_input, has_tag_input, has_creator_input, knows_input,
+            );
+        });
+
+        // add inputs
+        let mut next_time: usize = 1;
+        input_insert_vec(load_tag(path.as_str(), index, peers), &mut tag_input, next_time);
+        input_insert_vec(load_person_has_interest(path.as_str(), index, peers), &mut has_interest_input, next_time);
+
+        // insert hasTag relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasTag_tag_0_0.csv", path.as_str(), index, peers),
+            &mut has_tag_input,
+            next_time
+        );
+        // insert hasCreator relations
+        input_insert_vec(
+            load_dynamic_connection("dynamic/post_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            0 // do not advance just yet
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/comment_hasCreator_person_0_0.csv", path.as_str(), index, peers),
+            &mut has_creator_input,
+            next_time
+        );
+        input_insert_vec(
+            load_dynamic_connection("dynamic/person_knows_person_0_0.csv", path.as_str(), index, peers),
+            &mut knows_input,
+            next_time
+        );
+
+        eprintln!("LOADED;{:}", timer.elapsed().as_secs_f64());
+        timer = Instant::now();
+
+        // Compute...
+        while probe.less_than(tag_input.time()) {
+            worker.step();
+        }
+
+        eprintln!("CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print results
+        print_trace(&mut trace, next_time);
+
+        if change_path.eq(&"-".to_string()) {
+            eprintln!("No change set was given.");
+            return;
+        }
+
+        println!(" ---------------------------------------------------------------------- ");
+
+        // introduce change set
+        next_time += 1;
+        timer = Instant::now();
+
+        // parse change set file
+        for mut change_row in load_data(change_path.as_str(), index, peers) {
+            let create = match change_row.remove(0).as_str() {
+                "create" => true,
+                "remove" => false,
+                x => { panic!("Unknown change. It should be 'remove' or 'create': {}", x); }
+            };
+
+            let input = change_row.remove(0);
+
+            let mut row_iter = change_row.into_iter();
+            let created = parse_datetime(row_iter.next().unwrap());
+            let id1 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let id2 = row_iter.next().unwrap().parse::<Id>().unwrap();
+            let d = DynamicConnection::new(created, id1, id2);
+
+            match input.as_str() {
+                "person-hasinterest-tag" => {
+                    if create {
+                        has_interest_input.insert(d);
+                    } else {
+                        has_interest_input.remove(d);
+                    }
+                },
+                "person-knows-person" => {
+                    if create {
+                        knows_input.insert(d);
+                    } else {
+                        knows_input.remove(d);
+                    }
+                },
+                x => { panic!("Unknown change type: {}", x); }
+            }
+        }
+
+        // advance and flush all inputs...
+        tag_input.advance_to(next_time);
+        tag_input.flush();
+        has_interest_input.advance_to(next_time);
+        has_interest_input.flush();
+        has_tag_input.advance_to(next_time);
+        has_tag_input.flush();
+        has_creator_input.advance_to(next_time);
+        has_creator_input.flush();
+        knows_input.advance_to(next_time);
+        knows_input.flush();
+
+        // Compute change set...
+        while probe.less_than(&next_time) {
+            worker.step();
+        }
+
+        eprintln!("CHANGE_CALCULATED;{:.10}", timer.elapsed().as_secs_f64());
+
+        // print changed results
+        print_trace(&mut trace, next_time);
+    }).expect("Timely computation failed");
+}